@@ -1,96 +1,184 @@
 use crate::models::*;
+use crate::settings::Settings;
 use crate::storage::Storage;
-use crate::utils::parse_duration_from_string;
-use chrono::Local;
 use futures::{stream, StreamExt};
 use reqwest::{Client, StatusCode};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 const TEMPO_BASE_URL: &str = "https://api.tempo.io/4";
-const CONCURRENT_REQUESTS: usize = 5;
+const NETWORK_UNAVAILABLE: &str = "Network unavailable";
+// The largest page of results Tempo returns
+const MAX_PAGE_SIZE: u32 = 5000;
+// Seconds before an issue found deleted or not visible is asked for again
+const INACCESSIBLE_ISSUE_TTL: i64 = 24 * 60 * 60;
+// Issues resolved by a single Jira search
+const ISSUE_BATCH_SIZE: usize = 50;
+pub const CONCURRENT_REQUESTS: usize = 5;
 
 #[async_trait::async_trait]
 pub trait ApiTrait {
-    async fn log_time(
-        &self,
-        issue_key: &str,
-        time_spent: &str,
-        comment: Option<String>,
-    ) -> Result<WorklogItem, String>;
+    async fn log_time(&self, worklog: &NewWorklog) -> Result<WorklogItem, String>;
     async fn list_worklogs(&self, from: &str, to: &str) -> Result<Vec<WorklogItem>, String>;
+    async fn list_user_worklogs(
+        &self,
+        account_id: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<WorklogItem>, String>;
+    async fn list_worklogs_updated_from(
+        &self,
+        from: &str,
+        to: &str,
+        updated_from: &str,
+    ) -> Result<Vec<WorklogItem>, String>;
+    async fn list_issue_worklogs(
+        &self,
+        issue_id: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<WorklogItem>, String>;
+    async fn list_project_worklogs(
+        &self,
+        project_id: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<WorklogItem>, String>;
+    async fn get_time_tracking(&self, issue_key: &str) -> Result<TimeTracking, String>;
+    async fn get_jira_project(&self, project_key: &str) -> Result<JiraProject, String>;
+    async fn search_jira_issues(
+        &self,
+        jql: &str,
+        max_results: u32,
+    ) -> Result<Vec<JiraIssue>, String>;
     async fn delete_worklogs(&self, ids: &Vec<String>) -> Result<(), String>;
+    async fn get_worklog(&self, id: &str) -> Result<WorklogItem, String>;
+    async fn update_worklog(&self, id: &str, worklog: &NewWorklog) -> Result<WorklogItem, String>;
     async fn get_jira_issue(&self, issue_or_key: &str) -> Result<JiraIssue, String>;
+    async fn list_accounts(&self) -> Result<Vec<Account>, String>;
+    async fn get_project_account_links(&self, project_id: &str)
+        -> Result<Vec<AccountLink>, String>;
+    async fn get_periods(&self, from: &str, to: &str) -> Result<Vec<Period>, String>;
+    async fn get_timesheet_approval(
+        &self,
+        account_id: &str,
+        period: &Period,
+    ) -> Result<TimesheetApproval, String>;
+    async fn submit_timesheet(
+        &self,
+        period: &Period,
+        reviewer_account_id: Option<&str>,
+        comment: Option<&str>,
+    ) -> Result<TimesheetApproval, String>;
+    async fn get_jira_user(&self, account_id: &str) -> Result<JiraUser, String>;
+    async fn search_jira_users(&self, query: &str) -> Result<Vec<JiraUser>, String>;
+    async fn review_timesheet(
+        &self,
+        account_id: &str,
+        period: &Period,
+        review: TimesheetReview,
+        comment: Option<&str>,
+    ) -> Result<TimesheetApproval, String>;
+    async fn list_teams(&self) -> Result<Vec<Team>, String>;
+    async fn list_team_members(&self, team_id: i64) -> Result<Vec<TeamMember>, String>;
+    async fn get_user_schedule(
+        &self,
+        account_id: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<ScheduleDay>, String>;
 }
 
 pub struct ApiClient {
     client: Client,
+    tempo_url: String,
     pub storage: Storage,
     pub config: UserCredentials,
+    pub settings: Settings,
 }
 
 impl ApiClient {
-    pub fn new(storage: Storage) -> Self {
+    pub fn new(storage: Storage, settings: Settings) -> Self {
         let config = storage.get_credentials().unwrap();
 
         Self {
             client: Client::new(),
+            tempo_url: TEMPO_BASE_URL.to_string(),
             storage,
             config,
+            settings,
         }
     }
 
-    // Prefetch Jira issues concurrently
-    async fn prefetch_jira_issues_concurrently(
-        &self,
-        worklogs: &Vec<WorklogItem>,
-    ) -> Vec<JiraIssue> {
-        let mut issues = HashSet::new();
+    // Use a mock Tempo API in tests
+    #[cfg(test)]
+    pub fn with_tempo_url(mut self, tempo_url: &str) -> Self {
+        self.tempo_url = tempo_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    // Fetch the Jira issues of the worklogs that aren't cached yet. They're searched in batches,
+    // the ones a search doesn't return (e.g for lack of permission) are fetched one by one.
+    // If a search fails, the issues are left to be fetched when they're attached
+    pub async fn prefetch_jira_issues(&self, worklogs: &[WorklogItem]) -> Vec<JiraIssue> {
+        let mut missing: Vec<String> = worklogs
+            .iter()
+            .map(|worklog| worklog.issue.id.to_string())
+            .filter(|issue_id| {
+                self.storage.get_jira_issue(issue_id).is_none() && !self.is_inaccessible(issue_id)
+            })
+            .collect::<HashSet<String>>()
+            .into_iter()
+            .collect();
+        missing.sort();
+
+        let mut issues = Vec::new();
+
+        for batch in missing.chunks(ISSUE_BATCH_SIZE) {
+            let jql = format!("id in ({})", batch.join(","));
 
-        for worklog in worklogs {
-            issues.insert(worklog.issue.id.to_string());
+            // Without validation, Jira leaves out the issues that can't be seen instead of
+            // rejecting the whole query
+            match self.search_issues(&jql, batch.len() as u32, "warn").await {
+                Ok(found) => issues.extend(found),
+                Err(_) => return issues,
+            }
         }
 
-        stream::iter(issues.iter().cloned())
-            .map(|issue_id| async move {
-                match self.get_jira_issue(&issue_id).await {
-                    Ok(issue) => Some(issue),
-                    Err(e) => {
-                        eprintln!("Failed to fetch issue {}: {}", issue_id, e);
-                        None
-                    }
-                }
-            })
+        let found: HashSet<&str> = issues.iter().map(|issue| issue.id.as_str()).collect();
+        let leftover: Vec<String> = missing
+            .into_iter()
+            .filter(|issue_id| !found.contains(issue_id.as_str()))
+            .collect();
+
+        let fetched: Vec<JiraIssue> = stream::iter(leftover)
+            .map(|issue_id| async move { self.get_jira_issue(&issue_id).await.ok() })
             .buffer_unordered(CONCURRENT_REQUESTS)
             .filter_map(async move |res| res) // Only keep successful results
             .collect()
-            .await
+            .await;
+
+        issues.extend(fetched);
+        issues
     }
-}
 
-#[async_trait::async_trait]
-impl ApiTrait for ApiClient {
-    async fn log_time(
+    // `validate_query` is "warn" to get the issues found even if some in the query don't exist
+    async fn search_issues(
         &self,
-        issue_key: &str,
-        time_spent: &str,
-        comment: Option<String>,
-    ) -> Result<WorklogItem, String> {
-        let issue = self.get_jira_issue(&issue_key).await;
-
+        jql: &str,
+        max_results: u32,
+        validate_query: &str,
+    ) -> Result<Vec<JiraIssue>, String> {
         let response = self
             .client
-            .post(format!("{}/worklogs/", TEMPO_BASE_URL,))
-            .bearer_auth(&self.config.tempo_token)
-            .json(&serde_json::json!({
-                "authorAccountId": self.config.account_id,
-                "issueId": issue.map_err(|e| format!("{}", e))?.id,
-                "description": comment.unwrap_or_default(),
-                "startDate": Local::now().format("%Y-%m-%d").to_string(),
-                "timeSpentSeconds": parse_duration_from_string(time_spent)
-            }))
+            .get(format!("{}/rest/api/3/search", self.config.url))
+            .basic_auth(&self.config.jira_email, Some(&self.config.jira_token))
+            .query(&[
+                ("jql", jql),
+                ("fields", "summary,project"),
+                ("maxResults", max_results.to_string().as_str()),
+                ("validateQuery", validate_query),
+            ])
             .send()
             .await
-            .map_err(|e| format!("Request error: {}", e))?;
+            .map_err(request_error)?;
 
         let status = response.status();
         if !status.is_success() {
@@ -99,45 +187,196 @@ impl ApiTrait for ApiClient {
                 .await
                 .unwrap_or_else(|_| "Failed to read error body".to_string());
             return Err(format!(
-                "Failed to fetch worklogs: {}, {}",
+                "Failed to search issues: {}, {}",
                 status, error_body
             ));
         }
 
-        let json_data: WorklogItem = response
+        let json_data: JiraSearchResponse = response
             .json()
             .await
             .map_err(|e| format!("Failed to parse JSON: {}", e))?;
 
-        Ok(json_data)
+        for issue in &json_data.issues {
+            self.storage.store_jira_issue(issue);
+        }
+
+        Ok(json_data.issues)
     }
 
-    async fn list_worklogs(
+    // Whether the issue was found missing recently enough not to ask Jira again
+    fn is_inaccessible(&self, issue_or_key: &str) -> bool {
+        self.storage
+            .get_inaccessible_issue(issue_or_key)
+            .is_some_and(|checked_at| {
+                chrono::Local::now().timestamp() - checked_at < INACCESSIBLE_ISSUE_TTL
+            })
+    }
+
+    // Set the Jira issue of a worklog. Worklogs on issues that are deleted or not visible to
+    // the user are kept without one, and the issue isn't asked for again for a while
+    pub async fn attach_jira_issue(&self, worklog: &mut WorklogItem) -> Result<(), String> {
+        let issue_id = worklog.issue.id.to_string();
+
+        worklog.jira_issue = if self.is_inaccessible(&issue_id) {
+            None
+        } else {
+            match self.get_jira_issue(&issue_id).await {
+                Ok(issue) => Some(issue),
+                // Jira answered 403 or 404, recorded by `fetch_jira_issue`
+                Err(_) if self.is_inaccessible(&issue_id) => None,
+                Err(e) => return Err(e),
+            }
+        };
+
+        Ok(())
+    }
+
+    // Resolve several Jira issues concurrently, mapped by the requested key.
+    // Fails if any of them can't be found
+    pub async fn get_jira_issues_concurrently(
         &self,
-        from_date: &str,
-        to_date: &str,
+        issue_keys: &[String],
+    ) -> Result<HashMap<String, JiraIssue>, String> {
+        let unique_keys: HashSet<&String> = issue_keys.iter().collect();
+
+        let results: Vec<Result<(String, JiraIssue), String>> = stream::iter(unique_keys)
+            .map(|issue_key| async move {
+                self.get_jira_issue(issue_key)
+                    .await
+                    .map(|issue| (issue_key.clone(), issue))
+                    .map_err(|e| format!("{}: {}", issue_key, e))
+            })
+            .buffer_unordered(CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+
+        results.into_iter().collect()
+    }
+
+    // Build the body Tempo expects to create or update a worklog
+    async fn build_worklog_body(&self, worklog: &NewWorklog) -> Result<serde_json::Value, String> {
+        let issue = self.get_jira_issue(&worklog.issue_key).await?;
+
+        let mut body = serde_json::json!({
+            "authorAccountId": self.config.account_id,
+            "issueId": issue.id,
+            "description": worklog.description,
+            "startDate": worklog.start_date,
+            "timeSpentSeconds": worklog.time_spent_seconds
+        });
+
+        if let Some(start_time) = &worklog.start_time {
+            body["startTime"] = serde_json::json!(start_time);
+        }
+
+        if let Some(billable_seconds) = worklog.billable_seconds {
+            body["billableSeconds"] = serde_json::json!(billable_seconds);
+        }
+
+        if !worklog.attributes.is_empty() {
+            body["attributes"] = serde_json::json!(worklog.attributes);
+        }
+
+        if let Some(remaining_estimate_seconds) = worklog.remaining_estimate_seconds {
+            body["remainingEstimateSeconds"] = serde_json::json!(remaining_estimate_seconds);
+        }
+
+        Ok(body)
+    }
+
+    // Fetch Jira issue from Jira API, bypassing the cache, and store it
+    async fn fetch_jira_issue(&self, issue_or_key: &str) -> Result<JiraIssue, String> {
+        let url = format!("{}/rest/api/3/issue/{}", self.config.url, issue_or_key);
+
+        let client = self
+            .client
+            .get(&url)
+            .basic_auth(&self.config.jira_email, Some(&self.config.jira_token));
+
+        let response = client.send().await.map_err(request_error)?;
+        let status = response.status();
+
+        if !status.is_success() {
+            let error_message = response
+                .text()
+                .await
+                .ok()
+                .and_then(|body| {
+                    serde_json::from_str::<serde_json::Value>(&body)
+                        .ok()
+                        .and_then(|json| {
+                            json["errorMessages"]
+                                .as_array()
+                                .and_then(|msgs| msgs.first())
+                                .and_then(|msg| msg.as_str())
+                                .map(String::from)
+                        })
+                })
+                .unwrap_or_else(|| "Failed to read error response".to_string());
+
+            if status == StatusCode::NOT_FOUND || status == StatusCode::FORBIDDEN {
+                self.storage
+                    .store_inaccessible_issue(issue_or_key, chrono::Local::now().timestamp());
+            }
+
+            return Err(format!("{} {}", error_message, status));
+        }
+
+        let raw_response = response.text().await.expect("Failed to get response text");
+
+        let issue: JiraIssue = serde_json::from_str(&raw_response)
+            .map_err(|e| format!("Unable to retrieve Jira issue: {}", e))?;
+
+        self.storage.store_jira_issue(&issue);
+
+        Ok(issue)
+    }
+
+    // Get the Jira project of an issue. Issues cached before projects were stored are fetched again
+    pub async fn get_issue_project(&self, issue_key: &str) -> Result<JiraProject, String> {
+        let issue = match self.get_jira_issue(issue_key).await? {
+            JiraIssue {
+                fields: Some(fields),
+                ..
+            } => return Ok(fields.project),
+            issue => self.fetch_jira_issue(&issue.key).await?,
+        };
+
+        issue
+            .fields
+            .map(|fields| fields.project)
+            .ok_or_else(|| format!("No project found for issue {}", issue_key))
+    }
+
+    // Fetch all pages of a Tempo worklogs endpoint, with their Jira issues. The pages are
+    // followed through their `next` links
+    async fn fetch_worklogs(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
     ) -> Result<Vec<WorklogItem>, String> {
         let mut worklogs: Vec<WorklogItem> = Vec::new();
 
-        let mut offset = 0;
-        let limit = 50;
+        let limit = self
+            .settings
+            .tempo
+            .page_size
+            .clamp(1, MAX_PAGE_SIZE)
+            .to_string();
+        let mut request = self
+            .client
+            .get(format!("{}/{}", self.tempo_url, path))
+            .query(query)
+            .query(&[("offset", "0"), ("limit", limit.as_str())]);
+        let mut previous_offset = None;
 
         loop {
-            let response = self
-                .client
-                .get(format!(
-                    "{}/worklogs/user/{}",
-                    TEMPO_BASE_URL, self.config.account_id
-                ))
+            let response = request
                 .bearer_auth(&self.config.tempo_token)
-                .query(&[
-                    ("from", from_date),
-                    ("to", to_date),
-                    ("offset", offset.to_string().as_str()),
-                ])
                 .send()
                 .await
-                .map_err(|e| format!("Request error: {}", e))?;
+                .map_err(request_error)?;
 
             let status = response.status();
             if !status.is_success() {
@@ -149,40 +388,264 @@ impl ApiTrait for ApiClient {
                 .await
                 .map_err(|e| format!("Failed to parse JSON: {}", e))?;
 
-            if json_data.results.is_empty() {
-                break;
+            // A link back to a page already seen would loop forever
+            let offset = json_data.metadata.offset;
+            if previous_offset.is_some_and(|previous| offset <= previous) {
+                return Err(format!(
+                    "Failed to fetch worklogs: page at offset {} was returned twice",
+                    offset
+                ));
             }
+            previous_offset = Some(offset);
 
-            let _ = self
-                .prefetch_jira_issues_concurrently(&json_data.results)
-                .await;
+            let _ = self.prefetch_jira_issues(&json_data.results).await;
 
             for worklog in json_data.results.iter_mut() {
-                worklog.jira_issue =
-                    Some(self.get_jira_issue(&worklog.issue.id.to_string()).await?);
+                self.attach_jira_issue(worklog).await?;
             }
 
+            let next = json_data.metadata.next_page().map(str::to_string);
             worklogs.extend(json_data.results);
 
-            offset += limit;
+            match next {
+                Some(next) => request = self.client.get(next),
+                None => break,
+            }
         }
 
         Ok(worklogs)
     }
 
+    // Resolve a user given by email or account ID to the account ID. Emails are looked up
+    // with the Jira user search and cached
+    pub async fn resolve_user(&self, user: &str) -> Result<String, String> {
+        if !user.contains('@') {
+            return Ok(user.to_string());
+        }
+
+        if let Some(account_id) = self.storage.get_user_account_id(user) {
+            return Ok(account_id);
+        }
+
+        let users = self.search_jira_users(user).await?;
+        let account_id = find_user_by_email(&users, user)?.account_id.clone();
+
+        self.storage.store_user_account_id(user, &account_id);
+
+        Ok(account_id)
+    }
+
+    // Get work attribute definitions, from the cache unless `refresh` is set
+    pub async fn get_work_attributes(&self, refresh: bool) -> Result<Vec<WorkAttribute>, String> {
+        let cached = self.storage.get_work_attributes();
+
+        if !refresh && !cached.is_empty() {
+            return Ok(cached);
+        }
+
+        let response = self
+            .client
+            .get(format!("{}/work-attributes", self.tempo_url))
+            .bearer_auth(&self.config.tempo_token)
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Failed to fetch work attributes: {}", status));
+        }
+
+        let json_data: WorkAttributesResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        self.storage.store_work_attributes(&json_data.results);
+
+        Ok(self.storage.get_work_attributes())
+    }
+
+    // Check concurrently which of the issue keys exist in Jira
+    pub async fn find_existing_issues(&self, issue_keys: &[String]) -> HashSet<String> {
+        stream::iter(issue_keys.iter().collect::<HashSet<&String>>())
+            .map(|issue_key| async move { self.get_jira_issue(issue_key).await.ok() })
+            .buffer_unordered(CONCURRENT_REQUESTS)
+            .filter_map(async move |res| res.map(|issue| issue.key))
+            .collect()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiTrait for ApiClient {
+    async fn log_time(&self, worklog: &NewWorklog) -> Result<WorklogItem, String> {
+        let body = self.build_worklog_body(worklog).await?;
+
+        let response = self
+            .client
+            .post(format!("{}/worklogs/", self.tempo_url))
+            .bearer_auth(&self.config.tempo_token)
+            .json(&body)
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Failed to read error body".to_string());
+            return Err(format!(
+                "Failed to fetch worklogs: {}, {}",
+                status, error_body
+            ));
+        }
+
+        let json_data: WorklogItem = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        Ok(json_data)
+    }
+
+    async fn list_worklogs(
+        &self,
+        from_date: &str,
+        to_date: &str,
+    ) -> Result<Vec<WorklogItem>, String> {
+        self.list_user_worklogs(&self.config.account_id, from_date, to_date)
+            .await
+    }
+
+    async fn list_user_worklogs(
+        &self,
+        account_id: &str,
+        from_date: &str,
+        to_date: &str,
+    ) -> Result<Vec<WorklogItem>, String> {
+        self.fetch_worklogs(
+            &format!("worklogs/user/{}", account_id),
+            &[("from", from_date), ("to", to_date)],
+        )
+        .await
+    }
+
+    // Worklogs of the current user created or updated since `updated_from`
+    async fn list_worklogs_updated_from(
+        &self,
+        from_date: &str,
+        to_date: &str,
+        updated_from: &str,
+    ) -> Result<Vec<WorklogItem>, String> {
+        self.fetch_worklogs(
+            &format!("worklogs/user/{}", self.config.account_id),
+            &[
+                ("from", from_date),
+                ("to", to_date),
+                ("updatedFrom", updated_from),
+            ],
+        )
+        .await
+    }
+
+    async fn list_issue_worklogs(
+        &self,
+        issue_id: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<WorklogItem>, String> {
+        self.fetch_worklogs(&format!("worklogs/issue/{}", issue_id), query)
+            .await
+    }
+
+    async fn list_project_worklogs(
+        &self,
+        project_id: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<WorklogItem>, String> {
+        self.fetch_worklogs(&format!("worklogs/project/{}", project_id), query)
+            .await
+    }
+
+    // Search Jira issues with JQL. Found issues are stored in the cache
+    async fn search_jira_issues(
+        &self,
+        jql: &str,
+        max_results: u32,
+    ) -> Result<Vec<JiraIssue>, String> {
+        self.search_issues(jql, max_results, "strict").await
+    }
+
+    async fn get_jira_project(&self, project_key: &str) -> Result<JiraProject, String> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/rest/api/3/project/{}",
+                self.config.url, project_key
+            ))
+            .basic_auth(&self.config.jira_email, Some(&self.config.jira_token))
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!(
+                "Failed to fetch project {}: {}",
+                project_key, status
+            ));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))
+    }
+
+    async fn get_time_tracking(&self, issue_key: &str) -> Result<TimeTracking, String> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/rest/api/3/issue/{}",
+                self.config.url, issue_key
+            ))
+            .basic_auth(&self.config.jira_email, Some(&self.config.jira_token))
+            .query(&[("fields", "timetracking")])
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!(
+                "Failed to fetch estimates of {}: {}",
+                issue_key, status
+            ));
+        }
+
+        let json_data: JiraTimeTrackingResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        Ok(json_data.fields.timetracking)
+    }
+
     // Delete a worklog by its ID
     async fn delete_worklogs(&self, worklog_ids: &Vec<String>) -> Result<(), String> {
         for worklog_id in worklog_ids {
             let response = self
                 .client
-                .delete(&format!("{}/worklogs/{}", TEMPO_BASE_URL, worklog_id))
+                .delete(&format!("{}/worklogs/{}", self.tempo_url, worklog_id))
                 .bearer_auth(&self.config.tempo_token)
                 .json(&serde_json::json!({
                     "id": worklog_id
                 }))
                 .send()
                 .await
-                .map_err(|e| format!("Request error: {}", e))?;
+                .map_err(request_error)?;
 
             if response.status() != StatusCode::NO_CONTENT {
                 return Err(format!(
@@ -191,60 +654,752 @@ impl ApiTrait for ApiClient {
                     response.status()
                 ));
             }
+
+            self.storage.remove_mirrored_worklog(worklog_id);
         }
 
         Ok(())
     }
 
-    // Get Jira issue from Jira API and store it in the database by its id and key
-    async fn get_jira_issue(&self, issue_or_key: &str) -> Result<JiraIssue, String> {
-        if let Some(jira_issue) = self.storage.get_jira_issue(issue_or_key) {
-            return Ok(jira_issue);
-        }
-
-        let url = format!("{}/rest/api/3/issue/{}", self.config.url, issue_or_key);
-
-        let client = self
+    async fn get_worklog(&self, worklog_id: &str) -> Result<WorklogItem, String> {
+        let response = self
             .client
-            .get(&url)
-            .basic_auth(&self.config.jira_email, Some(&self.config.jira_token));
+            .get(format!("{}/worklogs/{}", self.tempo_url, worklog_id))
+            .bearer_auth(&self.config.tempo_token)
+            .send()
+            .await
+            .map_err(request_error)?;
 
-        let response = client.send().await.expect("JIRA request failed");
         let status = response.status();
+        if !status.is_success() {
+            return Err(format!(
+                "Failed to fetch worklog {}: {}",
+                worklog_id, status
+            ));
+        }
+
+        let mut worklog: WorklogItem = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        self.attach_jira_issue(&mut worklog).await?;
+
+        Ok(worklog)
+    }
+
+    async fn update_worklog(
+        &self,
+        worklog_id: &str,
+        worklog: &NewWorklog,
+    ) -> Result<WorklogItem, String> {
+        let body = self.build_worklog_body(worklog).await?;
+
+        let response = self
+            .client
+            .put(format!("{}/worklogs/{}", self.tempo_url, worklog_id))
+            .bearer_auth(&self.config.tempo_token)
+            .json(&body)
+            .send()
+            .await
+            .map_err(request_error)?;
 
+        let status = response.status();
         if !status.is_success() {
-            let error_message = response
+            let error_body = response
                 .text()
                 .await
-                .ok()
-                .and_then(|body| {
-                    serde_json::from_str::<serde_json::Value>(&body)
-                        .ok()
-                        .and_then(|json| {
-                            json["errorMessages"]
-                                .as_array()
-                                .and_then(|msgs| msgs.first())
-                                .and_then(|msg| msg.as_str())
-                                .map(String::from)
-                        })
-                })
-                .unwrap_or_else(|| "Failed to read error response".to_string());
+                .unwrap_or_else(|_| "Failed to read error body".to_string());
+            return Err(format!(
+                "Failed to update worklog {}: {}, {}",
+                worklog_id, status, error_body
+            ));
+        }
 
-            return Err(format!("{} {}", error_message, status));
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))
+    }
+
+    async fn list_accounts(&self) -> Result<Vec<Account>, String> {
+        let response = self
+            .client
+            .get(format!("{}/accounts", self.tempo_url))
+            .bearer_auth(&self.config.tempo_token)
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Failed to fetch accounts: {}", status));
         }
 
-        let raw_response = response.text().await.expect("Failed to get response text");
+        let json_data: AccountsResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
 
-        let json_data: JiraIssue = serde_json::from_str(&raw_response)
-            .map_err(|e| format!("Unable to retrieve Jira issue: {}", e))?;
+        Ok(json_data.results)
+    }
+
+    async fn get_project_account_links(
+        &self,
+        project_id: &str,
+    ) -> Result<Vec<AccountLink>, String> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/account-links/project/{}",
+                self.tempo_url, project_id
+            ))
+            .bearer_auth(&self.config.tempo_token)
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Failed to fetch account links: {}", status));
+        }
+
+        let json_data: AccountLinksResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        Ok(json_data.results)
+    }
 
-        let issue = JiraIssue {
-            key: json_data.key,
-            id: json_data.id,
+    async fn get_periods(&self, from_date: &str, to_date: &str) -> Result<Vec<Period>, String> {
+        let response = self
+            .client
+            .get(format!("{}/periods", self.tempo_url))
+            .bearer_auth(&self.config.tempo_token)
+            .query(&[("from", from_date), ("to", to_date)])
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Failed to fetch periods: {}", status));
+        }
+
+        let json_data: PeriodsResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        Ok(json_data.periods)
+    }
+
+    async fn get_timesheet_approval(
+        &self,
+        account_id: &str,
+        period: &Period,
+    ) -> Result<TimesheetApproval, String> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/timesheet-approvals/user/{}",
+                self.tempo_url, account_id
+            ))
+            .bearer_auth(&self.config.tempo_token)
+            .query(&[("from", &period.from), ("to", &period.to)])
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Failed to fetch timesheet approval: {}", status));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))
+    }
+
+    async fn submit_timesheet(
+        &self,
+        period: &Period,
+        reviewer_account_id: Option<&str>,
+        comment: Option<&str>,
+    ) -> Result<TimesheetApproval, String> {
+        let mut body = serde_json::json!({});
+
+        if let Some(reviewer_account_id) = reviewer_account_id {
+            body["reviewerAccountId"] = serde_json::json!(reviewer_account_id);
+        }
+
+        if let Some(comment) = comment {
+            body["comment"] = serde_json::json!(comment);
+        }
+
+        let response = self
+            .client
+            .post(format!(
+                "{}/timesheet-approvals/user/{}/submit",
+                self.tempo_url, self.config.account_id
+            ))
+            .bearer_auth(&self.config.tempo_token)
+            .query(&[("from", &period.from), ("to", &period.to)])
+            .json(&body)
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Failed to read error body".to_string());
+            return Err(format!(
+                "Failed to submit timesheet: {}, {}",
+                status, error_body
+            ));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))
+    }
+
+    async fn get_jira_user(&self, account_id: &str) -> Result<JiraUser, String> {
+        let response = self
+            .client
+            .get(format!("{}/rest/api/3/user", self.config.url))
+            .basic_auth(&self.config.jira_email, Some(&self.config.jira_token))
+            .query(&[("accountId", account_id)])
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!(
+                "Failed to fetch Jira user {}: {}",
+                account_id, status
+            ));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))
+    }
+
+    async fn search_jira_users(&self, query: &str) -> Result<Vec<JiraUser>, String> {
+        let response = self
+            .client
+            .get(format!("{}/rest/api/3/user/search", self.config.url))
+            .basic_auth(&self.config.jira_email, Some(&self.config.jira_token))
+            .query(&[("query", query)])
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Failed to search Jira users: {}", status));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))
+    }
+
+    async fn review_timesheet(
+        &self,
+        account_id: &str,
+        period: &Period,
+        review: TimesheetReview,
+        comment: Option<&str>,
+    ) -> Result<TimesheetApproval, String> {
+        let mut body = serde_json::json!({});
+
+        if let Some(comment) = comment {
+            body["comment"] = serde_json::json!(comment);
+        }
+
+        let response = self
+            .client
+            .post(format!(
+                "{}/timesheet-approvals/user/{}/{}",
+                self.tempo_url,
+                account_id,
+                review.as_str()
+            ))
+            .bearer_auth(&self.config.tempo_token)
+            .query(&[("from", &period.from), ("to", &period.to)])
+            .json(&body)
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Failed to read error body".to_string());
+            return Err(format!(
+                "Failed to {} timesheet: {}, {}",
+                review.as_str(),
+                status,
+                error_body
+            ));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))
+    }
+
+    async fn list_teams(&self) -> Result<Vec<Team>, String> {
+        let response = self
+            .client
+            .get(format!("{}/teams", self.tempo_url))
+            .bearer_auth(&self.config.tempo_token)
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Failed to fetch teams: {}", status));
+        }
+
+        let json_data: TeamsResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        Ok(json_data.results)
+    }
+
+    async fn list_team_members(&self, team_id: i64) -> Result<Vec<TeamMember>, String> {
+        let response = self
+            .client
+            .get(format!("{}/teams/{}/members", self.tempo_url, team_id))
+            .bearer_auth(&self.config.tempo_token)
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Failed to fetch team members: {}", status));
+        }
+
+        let json_data: TeamMembersResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        Ok(json_data.results)
+    }
+
+    async fn get_user_schedule(
+        &self,
+        account_id: &str,
+        from_date: &str,
+        to_date: &str,
+    ) -> Result<Vec<ScheduleDay>, String> {
+        let response = self
+            .client
+            .get(format!("{}/user-schedule/{}", self.tempo_url, account_id))
+            .bearer_auth(&self.config.tempo_token)
+            .query(&[("from", from_date), ("to", to_date)])
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Failed to fetch user schedule: {}", status));
+        }
+
+        let json_data: UserScheduleResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        Ok(json_data.results)
+    }
+
+    // Get Jira issue from Jira API and store it in the database by its id and key.
+    // Aliases are expanded to the issue key they point to
+    async fn get_jira_issue(&self, issue_or_key: &str) -> Result<JiraIssue, String> {
+        let issue_or_key = &self.storage.expand_alias(issue_or_key);
+
+        if let Some(jira_issue) = self.storage.get_jira_issue(issue_or_key) {
+            return Ok(jira_issue);
+        }
+
+        self.fetch_jira_issue(issue_or_key).await
+    }
+}
+
+// Connection failures and timeouts are told apart from other errors, so the changes can be queued
+fn request_error(error: reqwest::Error) -> String {
+    if error.is_connect() || error.is_timeout() {
+        format!("{}: {}", NETWORK_UNAVAILABLE, error)
+    } else {
+        format!("Request error: {}", error)
+    }
+}
+
+pub fn is_offline_error(error: &str) -> bool {
+    error.contains(NETWORK_UNAVAILABLE)
+}
+
+// Pick the searched user with the email. Jira hides emails of some users, so when none
+// matches exactly the candidates are listed to pick an account ID from
+fn find_user_by_email<'a>(users: &'a [JiraUser], email: &str) -> Result<&'a JiraUser, String> {
+    if let Some(user) = users.iter().find(|user| {
+        user.email_address
+            .as_deref()
+            .is_some_and(|address| address.eq_ignore_ascii_case(email))
+    }) {
+        return Ok(user);
+    }
+
+    if users.is_empty() {
+        return Err(format!("No Jira user found for {}", email));
+    }
+
+    let candidates: Vec<String> = users
+        .iter()
+        .map(|user| format!("{} ({})", user.display_name, user.account_id))
+        .collect();
+    Err(format!(
+        "No Jira user with the email {}, use one of these account IDs instead:\n  {}",
+        email,
+        candidates.join("\n  ")
+    ))
+}
+
+// Fresh test database with the credentials of user test123 on the Jira at `url`, and issue
+// ABC-1 (id 10001) cached
+#[cfg(test)]
+pub fn create_test_storage(path: &str, url: &str) -> Storage {
+    let _ = std::fs::remove_dir_all(path);
+    let storage = Storage::with_path(path);
+
+    storage.store_credentials(UserCredentials {
+        url: url.to_string(),
+        account_id: "test123".to_string(),
+        tempo_token: "test-tempo-token".to_string(),
+        jira_token: "test-jira-token".to_string(),
+        jira_email: "test@example.com".to_string(),
+    });
+    storage.store_jira_issue(&JiraIssue {
+        id: "10001".to_string(),
+        key: "ABC-1".to_string(),
+        fields: None,
+    });
+
+    storage
+}
+
+// Client of a mock server at `url` standing in for both Jira and Tempo
+#[cfg(test)]
+pub fn create_test_api(path: &str, url: &str) -> ApiClient {
+    ApiClient::new(create_test_storage(path, url), Settings::default()).with_tempo_url(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    fn create_paged_test_api(path: &str, url: &str, page_size: u32) -> ApiClient {
+        let mut api = create_test_api(path, url);
+        api.settings.tempo.page_size = page_size;
+
+        api
+    }
+
+    // A page of worklogs with consecutive IDs starting at `offset` + 1
+    fn page(offset: u32, count: u32, limit: u32, next: Option<String>) -> String {
+        let results: Vec<String> = (offset + 1..=offset + count)
+            .map(|id| {
+                format!(
+                    r#"{{"tempoWorklogId": {}, "timeSpentSeconds": 3600, "description": "",
+                        "startDate": "2025-04-07", "createdAt": "2025-04-07T10:00:00Z",
+                        "issue": {{"id": 10001}}}}"#,
+                    id
+                )
+            })
+            .collect();
+
+        serde_json::json!({
+            "metadata": {"count": count, "offset": offset, "limit": limit, "next": next},
+            "results": serde_json::from_str::<serde_json::Value>(&format!("[{}]", results.join(",")))
+                .unwrap(),
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_list_worklogs_follows_next_links() {
+        let test_db_path = "test_list_worklogs_pages";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_paged_test_api(test_db_path, &server.url(), 2);
+        let url = server.url();
+        let next = |offset: u32| {
+            Some(format!(
+                "{}/worklogs/user/test123?from=2025-04-01&to=2025-04-30&offset={}&limit=2",
+                url, offset
+            ))
         };
 
-        self.storage.store_jira_issue(&issue);
+        let pages = vec![
+            server
+                .mock("GET", "/worklogs/user/test123")
+                .match_query(Matcher::Regex("offset=0&limit=2$".to_string()))
+                .with_body(page(0, 2, 2, next(2)))
+                .create_async()
+                .await,
+            server
+                .mock("GET", "/worklogs/user/test123")
+                .match_query(Matcher::Regex("offset=2&limit=2$".to_string()))
+                .with_body(page(2, 2, 2, next(4)))
+                .create_async()
+                .await,
+            // Short last page. Its link is ignored
+            server
+                .mock("GET", "/worklogs/user/test123")
+                .match_query(Matcher::Regex("offset=4&limit=2$".to_string()))
+                .with_body(page(4, 1, 2, next(6)))
+                .create_async()
+                .await,
+        ];
 
-        Ok(issue)
+        let worklogs = api.list_worklogs("2025-04-01", "2025-04-30").await.unwrap();
+        let ids: Vec<i64> = worklogs.iter().map(|w| w.tempo_worklog_id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+        assert_eq!(worklogs[0].jira_issue.as_ref().unwrap().key, "ABC-1");
+        for page in pages {
+            page.assert_async().await;
+        }
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    #[tokio::test]
+    async fn test_list_worklogs_page_size() {
+        let test_db_path = "test_list_worklogs_page_size";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_paged_test_api(test_db_path, &server.url(), 100_000);
+
+        // The page size is capped to Tempo's maximum
+        let capped = server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(Matcher::Regex("offset=0&limit=5000$".to_string()))
+            .with_body(page(0, 1, 5000, None))
+            .create_async()
+            .await;
+
+        let worklogs = api.list_worklogs("2025-04-01", "2025-04-30").await.unwrap();
+        assert_eq!(worklogs.len(), 1);
+        capped.assert_async().await;
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    #[tokio::test]
+    async fn test_list_worklogs_repeated_page() {
+        let test_db_path = "test_list_worklogs_repeated_page";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_paged_test_api(test_db_path, &server.url(), 2);
+
+        // A link back to the same page is an error rather than an endless loop
+        let same_page = format!("{}/worklogs/user/test123?offset=0&limit=2", server.url());
+        let repeated = server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(Matcher::Regex("offset=0&limit=2$".to_string()))
+            .with_body(page(0, 2, 2, Some(same_page)))
+            .expect(2)
+            .create_async()
+            .await;
+
+        let error = api
+            .list_worklogs("2025-04-01", "2025-04-30")
+            .await
+            .unwrap_err();
+        assert!(error.contains("returned twice"));
+        repeated.assert_async().await;
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_jira_issues() {
+        let test_db_path = "test_prefetch_jira_issues";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_paged_test_api(test_db_path, &server.url(), 50);
+
+        let worklogs: Vec<WorklogItem> = [10001, 10002, 10003, 10004, 10002]
+            .iter()
+            .map(|issue_id| {
+                serde_json::from_value(serde_json::json!({
+                    "tempoWorklogId": 1, "timeSpentSeconds": 3600, "description": "",
+                    "startDate": "2025-04-07", "createdAt": "2025-04-07T10:00:00Z",
+                    "issue": {"id": issue_id}
+                }))
+                .unwrap()
+            })
+            .collect();
+
+        // 10001 is cached, 10004 can't be found by the search
+        let search = server
+            .mock("GET", "/rest/api/3/search")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("jql".to_string(), "id in (10002,10003,10004)".to_string()),
+                Matcher::UrlEncoded("validateQuery".to_string(), "warn".to_string()),
+            ]))
+            .with_body(
+                serde_json::json!({"issues": [
+                    {"id": "10002", "key": "ABC-2",
+                     "fields": {"summary": "Login", "project": {"id": "1", "key": "ABC"}}},
+                    {"id": "10003", "key": "ABC-3",
+                     "fields": {"summary": "Signup", "project": {"id": "1", "key": "ABC"}}}
+                ]})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let single = server
+            .mock("GET", "/rest/api/3/issue/10004")
+            .with_body(r#"{"id": "10004", "key": "ABC-4"}"#)
+            .create_async()
+            .await;
+
+        let mut keys: Vec<String> = api
+            .prefetch_jira_issues(&worklogs)
+            .await
+            .into_iter()
+            .map(|issue| issue.key)
+            .collect();
+        keys.sort();
+        assert_eq!(keys, vec!["ABC-2", "ABC-3", "ABC-4"]);
+        search.assert_async().await;
+        single.assert_async().await;
+
+        for issue_id in ["10002", "10003", "10004"] {
+            assert!(api.storage.get_jira_issue(issue_id).is_some());
+        }
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    fn listed_worklog(issue_id: i64) -> WorklogItem {
+        serde_json::from_value(serde_json::json!({
+            "tempoWorklogId": 1, "timeSpentSeconds": 3600, "description": "",
+            "startDate": "2025-04-07", "createdAt": "2025-04-07T10:00:00Z",
+            "issue": {"id": issue_id}
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_worklogs_inaccessible_issue() {
+        let test_db_path = "test_list_worklogs_inaccessible_issue";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_paged_test_api(test_db_path, &server.url(), 50);
+
+        let worklogs = server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(Matcher::Any)
+            .with_body(
+                serde_json::json!({"results": [{
+                    "tempoWorklogId": 1, "timeSpentSeconds": 3600, "description": "",
+                    "startDate": "2025-04-07", "createdAt": "2025-04-07T10:00:00Z",
+                    "issue": {"id": 10009}
+                }]})
+                .to_string(),
+            )
+            .expect(2)
+            .create_async()
+            .await;
+        let search = server
+            .mock("GET", "/rest/api/3/search")
+            .match_query(Matcher::Any)
+            .with_status(400)
+            .expect(1)
+            .create_async()
+            .await;
+        let issue = server
+            .mock("GET", "/rest/api/3/issue/10009")
+            .with_status(404)
+            .with_body(r#"{"errorMessages": ["Issue does not exist"]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        // The worklog is listed without its issue, which isn't asked for again
+        for _ in 0..2 {
+            let listed = api.list_worklogs("2025-04-01", "2025-04-30").await.unwrap();
+            assert_eq!(listed.len(), 1);
+            assert!(listed[0].jira_issue.is_none());
+        }
+        worklogs.assert_async().await;
+        search.assert_async().await;
+        issue.assert_async().await;
+
+        // Other errors aren't taken for an inaccessible issue
+        let failing = server
+            .mock("GET", "/rest/api/3/issue/10010")
+            .with_status(500)
+            .create_async()
+            .await;
+        let mut worklog = listed_worklog(10010);
+        assert!(api.attach_jira_issue(&mut worklog).await.is_err());
+        assert!(!api.is_inaccessible("10010"));
+        failing.assert_async().await;
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    #[test]
+    fn test_find_user_by_email() {
+        let user = |account_id: &str, name: &str, email: Option<&str>| JiraUser {
+            account_id: account_id.to_string(),
+            display_name: name.to_string(),
+            email_address: email.map(str::to_string),
+        };
+        let users = vec![
+            user("1", "Jane Doe", Some("jane.doe@example.com")),
+            user("2", "Jane Roe", None),
+        ];
+
+        assert_eq!(
+            find_user_by_email(&users, "Jane.Doe@example.com")
+                .unwrap()
+                .account_id,
+            "1"
+        );
+
+        // A single hidden email isn't taken as the match
+        let error = find_user_by_email(&users[1..], "jane@example.com").unwrap_err();
+        assert!(error.contains("Jane Roe (2)"));
+
+        assert!(find_user_by_email(&[], "jane@example.com")
+            .unwrap_err()
+            .contains("No Jira user found"));
     }
 }
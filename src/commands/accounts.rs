@@ -0,0 +1,331 @@
+use crate::api::{is_offline_error, ApiClient, ApiTrait};
+use crate::models::{Account, AccountLink, JiraProject, WorkAttributeValue};
+
+use dialoguer::Select;
+use std::collections::HashMap;
+
+// Work attribute Tempo keeps the worklog account in
+const ACCOUNT_ATTRIBUTE: &str = "_Account_";
+
+// Accounts linked to a project
+#[derive(Debug)]
+enum LinkedAccounts {
+    None,
+    Default(Account),
+    Several(Vec<Account>),
+}
+
+// Find the account to log time on the issue with, see `resolve_accounts`
+pub async fn resolve_account(
+    api: &ApiClient,
+    issue_key: &str,
+    account_key: Option<&str>,
+) -> Result<Option<Account>, String> {
+    let mut accounts = resolve_accounts(api, &[issue_key.to_string()], account_key).await?;
+
+    Ok(accounts.remove(issue_key).flatten())
+}
+
+// Find the account to log time on each issue with, once per project. A given account key must
+// exist and be linked to the project, unless it's global. Otherwise the project's default
+// account is used, and the user picks one if several are linked
+pub async fn resolve_accounts(
+    api: &ApiClient,
+    issue_keys: &[String],
+    account_key: Option<&str>,
+) -> Result<HashMap<String, Option<Account>>, String> {
+    // Fetched once, when needed
+    let mut accounts: Option<Vec<Account>> = None;
+
+    let given = match account_key {
+        Some(account_key) => {
+            let all = accounts.insert(api.list_accounts().await?);
+            let account = all
+                .iter()
+                .find(|account| account.key.eq_ignore_ascii_case(account_key))
+                .filter(|account| account.status != "CLOSED")
+                .ok_or_else(|| format!("Account {} doesn't exist or is closed", account_key))?;
+            Some(account.clone())
+        }
+        None => None,
+    };
+
+    let mut by_project: HashMap<String, Option<Account>> = HashMap::new();
+    let mut resolved: HashMap<String, Option<Account>> = HashMap::new();
+
+    for issue_key in issue_keys {
+        if resolved.contains_key(issue_key) {
+            continue;
+        }
+
+        let project = match api.get_issue_project(issue_key).await {
+            Ok(project) => project,
+            Err(e) if given.is_some() || is_offline_error(&e) => return Err(e),
+            // Accounts are optional, so failing to look them up doesn't stop logging. Without
+            // network it's left to `sync`, though
+            Err(_) => {
+                resolved.insert(issue_key.clone(), None);
+                continue;
+            }
+        };
+
+        let account = match by_project.get(&project.id) {
+            Some(account) => account.clone(),
+            None => {
+                let account = match &given {
+                    Some(account) => Some(check_linked(api, account, &project).await?),
+                    None => find_default_account(api, issue_key, &project, &mut accounts).await?,
+                };
+                by_project.insert(project.id.clone(), account.clone());
+                account
+            }
+        };
+
+        resolved.insert(issue_key.clone(), account);
+    }
+
+    Ok(resolved)
+}
+
+// Global accounts can be used on any project, the others have to be linked to it
+async fn check_linked(
+    api: &ApiClient,
+    account: &Account,
+    project: &JiraProject,
+) -> Result<Account, String> {
+    if account.global {
+        return Ok(account.clone());
+    }
+
+    let links = api.get_project_account_links(&project.id).await?;
+
+    if !links.iter().any(|link| link.account.id == account.id) {
+        return Err(format!(
+            "Account {} isn't linked to project {}",
+            account.key, project.key
+        ));
+    }
+
+    Ok(account.clone())
+}
+
+async fn find_default_account(
+    api: &ApiClient,
+    issue_key: &str,
+    project: &JiraProject,
+    accounts: &mut Option<Vec<Account>>,
+) -> Result<Option<Account>, String> {
+    let links = match api.get_project_account_links(&project.id).await {
+        Ok(links) => links,
+        Err(e) if is_offline_error(&e) => return Err(e),
+        Err(_) => return Ok(None),
+    };
+
+    if links.is_empty() {
+        return Ok(None);
+    }
+
+    if accounts.is_none() {
+        *accounts = Some(match api.list_accounts().await {
+            Ok(all) => all,
+            Err(e) if is_offline_error(&e) => return Err(e),
+            Err(_) => Vec::new(),
+        });
+    }
+
+    match find_linked_accounts(&links, accounts.as_deref().unwrap_or_default()) {
+        LinkedAccounts::None => Ok(None),
+        LinkedAccounts::Default(account) => Ok(Some(account)),
+        LinkedAccounts::Several(accounts) => Ok(Some(select_account(issue_key, accounts)?)),
+    }
+}
+
+pub fn account_attribute(account: &Account) -> WorkAttributeValue {
+    WorkAttributeValue {
+        key: ACCOUNT_ATTRIBUTE.to_string(),
+        value: account.key.clone(),
+    }
+}
+
+// A single linked account is the default one, even if it isn't marked as such
+fn find_linked_accounts(links: &[AccountLink], accounts: &[Account]) -> LinkedAccounts {
+    let linked: Vec<(&AccountLink, &Account)> = links
+        .iter()
+        .filter_map(|link| {
+            accounts
+                .iter()
+                .find(|account| account.id == link.account.id && account.status != "CLOSED")
+                .map(|account| (link, account))
+        })
+        .collect();
+
+    if let Some((_, account)) = linked.iter().find(|(link, _)| link.default) {
+        return LinkedAccounts::Default((*account).clone());
+    }
+
+    match linked.as_slice() {
+        [] => LinkedAccounts::None,
+        [(_, account)] => LinkedAccounts::Default((*account).clone()),
+        _ => LinkedAccounts::Several(
+            linked
+                .into_iter()
+                .map(|(_, account)| account.clone())
+                .collect(),
+        ),
+    }
+}
+
+fn select_account(issue_key: &str, accounts: Vec<Account>) -> Result<Account, String> {
+    let items: Vec<String> = accounts
+        .iter()
+        .map(|account| format!("{} - {}", account.key, account.name))
+        .collect();
+
+    let choice = Select::new()
+        .with_prompt(format!(
+            "Several accounts are linked to {}, pick one",
+            issue_key
+        ))
+        .items(&items)
+        .default(0)
+        .interact_opt()
+        .map_err(|e| format!("Failed to pick an account: {}", e))?
+        .ok_or("No account picked")?;
+
+    Ok(accounts.into_iter().nth(choice).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::create_test_api;
+    use crate::models::{AccountRef, JiraIssue, JiraIssueFields};
+
+    fn account(id: i64, key: &str, status: &str) -> Account {
+        Account {
+            id,
+            key: key.to_string(),
+            name: format!("{} account", key),
+            status: status.to_string(),
+            global: false,
+        }
+    }
+
+    fn link(id: i64, default: bool) -> AccountLink {
+        AccountLink {
+            account: AccountRef { id },
+            default,
+        }
+    }
+
+    #[test]
+    fn test_find_linked_accounts() {
+        let accounts = vec![
+            account(1, "DEV", "OPEN"),
+            account(2, "OPS", "OPEN"),
+            account(3, "OLD", "CLOSED"),
+        ];
+        let keys = |linked: LinkedAccounts| match linked {
+            LinkedAccounts::None => vec![],
+            LinkedAccounts::Default(account) => vec![account.key],
+            LinkedAccounts::Several(accounts) => accounts.into_iter().map(|a| a.key).collect(),
+        };
+
+        assert!(keys(find_linked_accounts(&[], &accounts)).is_empty());
+        assert_eq!(
+            keys(find_linked_accounts(
+                &[link(1, false), link(2, true)],
+                &accounts
+            )),
+            vec!["OPS"]
+        );
+        assert_eq!(
+            keys(find_linked_accounts(&[link(1, false)], &accounts)),
+            vec!["DEV"]
+        );
+        assert!(matches!(
+            find_linked_accounts(&[link(1, false), link(2, false)], &accounts),
+            LinkedAccounts::Several(_)
+        ));
+
+        // Closed and unknown accounts are ignored
+        assert_eq!(
+            keys(find_linked_accounts(
+                &[link(1, false), link(3, true), link(9, false)],
+                &accounts
+            )),
+            vec!["DEV"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_accounts() {
+        let test_db_path = "test_resolve_accounts";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_test_api(test_db_path, &server.url());
+
+        for (id, key, project_id) in [
+            ("1", "ABC-1", "100"),
+            ("2", "ABC-2", "100"),
+            ("3", "XYZ-1", "200"),
+        ] {
+            api.storage.store_jira_issue(&JiraIssue {
+                id: id.to_string(),
+                key: key.to_string(),
+                fields: Some(JiraIssueFields {
+                    project: JiraProject {
+                        id: project_id.to_string(),
+                        key: key.split('-').next().unwrap().to_string(),
+                    },
+                    summary: None,
+                }),
+            });
+        }
+
+        let accounts = server
+            .mock("GET", "/accounts")
+            .with_body(
+                r#"{"results": [
+                    {"id": 1, "key": "DEV", "name": "Development", "status": "OPEN"},
+                    {"id": 2, "key": "OPS", "name": "Operations", "status": "OPEN"},
+                    {"id": 3, "key": "GLB", "name": "Global", "status": "OPEN", "global": true}
+                ]}"#,
+            )
+            .expect(3)
+            .create_async()
+            .await;
+        let links = server
+            .mock("GET", "/account-links/project/100")
+            .with_body(r#"{"results": [{"account": {"id": 1}, "default": true}]}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let issue_keys =
+            |keys: &[&str]| -> Vec<String> { keys.iter().map(|k| k.to_string()).collect() };
+
+        // Issues of the same project share the account, looked up once
+        let resolved = resolve_accounts(&api, &issue_keys(&["ABC-1", "ABC-2"]), Some("dev"))
+            .await
+            .unwrap();
+        assert_eq!(resolved["ABC-1"].as_ref().unwrap().key, "DEV");
+        assert_eq!(resolved["ABC-2"].as_ref().unwrap().key, "DEV");
+
+        // A given account must be linked to the project, unless it's global
+        assert_eq!(
+            resolve_accounts(&api, &issue_keys(&["ABC-1"]), Some("OPS"))
+                .await
+                .unwrap_err(),
+            "Account OPS isn't linked to project ABC"
+        );
+        let resolved = resolve_accounts(&api, &issue_keys(&["XYZ-1"]), Some("GLB"))
+            .await
+            .unwrap();
+        assert_eq!(resolved["XYZ-1"].as_ref().unwrap().key, "GLB");
+
+        accounts.assert_async().await;
+        links.assert_async().await;
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::models::Alias;
+use crate::storage::Storage;
+
+use tabled::{builder::Builder, settings::Style};
+
+pub fn add_alias(
+    storage: &Storage,
+    name: &str,
+    issue_key: &str,
+    description: Option<String>,
+    duration: Option<String>,
+    billable: Option<bool>,
+) {
+    if let Err(e) = validate_alias_name(name) {
+        eprintln!("Error. {}", e);
+        return;
+    }
+
+    storage.store_alias(&Alias {
+        name: name.to_string(),
+        issue_key: issue_key.to_string(),
+        description,
+        duration,
+        billable,
+    });
+
+    println!("Alias '{}' now points to {}", name, issue_key);
+}
+
+pub fn list_aliases(storage: &Storage) {
+    let aliases = storage.list_aliases();
+
+    if aliases.is_empty() {
+        println!("No aliases yet. Run 'tempie alias add NAME ISSUE_KEY' to add one");
+        return;
+    }
+
+    println!("{}", build_aliases_table(&aliases));
+}
+
+pub fn remove_alias(storage: &Storage, name: &str) {
+    if storage.delete_alias(name) {
+        println!("Alias '{}' removed", name);
+    } else {
+        eprintln!("Error. Alias '{}' doesn't exist", name);
+    }
+}
+
+// Alias names can't clash with the issue list syntax used by `tempie log`, and can't look like
+// issue keys or ids, since aliases are expanded before issues are looked up
+fn validate_alias_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name == "." || name.contains([',', ':', ' ']) {
+        return Err(format!("Invalid alias name: '{}'", name));
+    }
+
+    if is_issue_key_or_id(name) {
+        return Err(format!(
+            "Invalid alias name: '{}' would hide the Jira issue with that key",
+            name
+        ));
+    }
+
+    Ok(())
+}
+
+// e.g "ABC-12", "abc-12" or "10001"
+fn is_issue_key_or_id(name: &str) -> bool {
+    let is_number = |value: &str| !value.is_empty() && value.chars().all(|c| c.is_ascii_digit());
+
+    match name.rsplit_once('-') {
+        Some((project, number)) => {
+            project.starts_with(|c: char| c.is_ascii_alphabetic())
+                && project
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && is_number(number)
+        }
+        None => is_number(name),
+    }
+}
+
+fn build_aliases_table(aliases: &[Alias]) -> String {
+    let mut builder = Builder::default();
+
+    builder.push_record(vec![
+        "Alias",
+        "Issue",
+        "Description",
+        "Duration",
+        "Billable",
+    ]);
+
+    for alias in aliases {
+        builder.push_record(vec![
+            alias.name.as_str(),
+            alias.issue_key.as_str(),
+            alias.description.as_deref().unwrap_or_default(),
+            alias.duration.as_deref().unwrap_or_default(),
+            match alias.billable {
+                Some(true) => "yes",
+                Some(false) => "no",
+                None => "",
+            },
+        ]);
+    }
+
+    builder.build().with(Style::modern()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_alias_name() {
+        assert!(validate_alias_name("standup").is_ok());
+        assert!(validate_alias_name("team-sync").is_ok());
+
+        assert!(validate_alias_name("").is_err());
+        assert!(validate_alias_name(".").is_err());
+        assert!(validate_alias_name("a,b").is_err());
+        assert!(validate_alias_name("a:50%").is_err());
+        assert!(validate_alias_name("daily standup").is_err());
+
+        // Issue keys and ids would be hidden by the alias
+        assert!(validate_alias_name("ABC-12").is_err());
+        assert!(validate_alias_name("abc-12").is_err());
+        assert!(validate_alias_name("MY_PROJ2-1").is_err());
+        assert!(validate_alias_name("10001").is_err());
+        assert!(validate_alias_name("sprint-12b").is_ok());
+        assert!(validate_alias_name("2-step").is_ok());
+    }
+
+    #[test]
+    fn test_build_aliases_table() {
+        let table = build_aliases_table(&[Alias {
+            name: "standup".to_string(),
+            issue_key: "INTERNAL-4821".to_string(),
+            description: Some("Daily standup".to_string()),
+            duration: Some("15m".to_string()),
+            billable: Some(false),
+        }]);
+
+        assert!(table.contains("Alias"));
+        assert!(table.contains("standup"));
+        assert!(table.contains("INTERNAL-4821"));
+        assert!(table.contains("Daily standup"));
+        assert!(table.contains("15m"));
+        assert!(table.contains("no"));
+    }
+}
@@ -0,0 +1,151 @@
+use crate::api::{ApiClient, ApiTrait};
+use crate::models::{Period, TimesheetApproval};
+use crate::utils::format_duration;
+
+use spinners::{Spinner, Spinners};
+
+pub async fn submit(
+    api: &ApiClient,
+    date: &str,
+    reviewer: Option<String>,
+    comment: Option<String>,
+) {
+    let mut spinner = Spinner::new(Spinners::Dots, "Submitting timesheet...".to_string());
+
+    let result = match find_period(api, date).await {
+        Ok(period) => {
+            api.submit_timesheet(&period, reviewer.as_deref(), comment.as_deref())
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(approval) => spinner.stop_with_message(format!(
+            "\nTimesheet {} to {} submitted, status: {}",
+            approval.period.from,
+            approval.period.to,
+            approval.status.label()
+        )),
+        Err(e) => spinner.stop_with_message(format!("\nError. {}", e)),
+    }
+}
+
+pub async fn approval_status(api: &ApiClient, date: &str) {
+    let mut spinner = Spinner::new(Spinners::Dots, "Retrieving approval status...".to_string());
+
+    let result = match find_period(api, date).await {
+        Ok(period) => {
+            api.get_timesheet_approval(&api.config.account_id, &period)
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(approval) => {
+            let reviewer = reviewer_name(api, &approval).await;
+            let mut lines = vec![
+                format!(
+                    "Timesheet {} to {}",
+                    approval.period.from, approval.period.to
+                ),
+                format!("Status: {}", approval.status.label()),
+                format!(
+                    "Logged: {}/{}",
+                    format_duration(approval.time_spent_seconds),
+                    format_duration(approval.required_seconds)
+                ),
+            ];
+            if let Some(reviewer) = reviewer {
+                lines.push(format!("Reviewer: {}", reviewer));
+            }
+            if let Some(comment) = approval.status.comment.filter(|c| !c.is_empty()) {
+                lines.push(format!("Comment: {}", comment));
+            }
+
+            spinner.stop_with_message(format!("\n{}", lines.join("\n")));
+        }
+        Err(e) => spinner.stop_with_message(format!("\nError. {}", e)),
+    }
+}
+
+// Status of the period containing the date, e.g "timesheet approved by Jane Doe".
+// None if approvals can't be fetched, so listings still work without them
+pub async fn approval_summary(api: &ApiClient, account_id: &str, date: &str) -> Option<String> {
+    let period = find_period(api, date).await.ok()?;
+    let approval = api.get_timesheet_approval(account_id, &period).await.ok()?;
+
+    Some(match reviewer_name(api, &approval).await {
+        Some(reviewer) => format!("timesheet {} by {}", approval.status.label(), reviewer),
+        None => format!("timesheet {}", approval.status.label()),
+    })
+}
+
+// Approved periods overlapping the dates. Errors are ignored, as not every Tempo
+// instance uses approvals
+pub async fn approved_periods(api: &ApiClient, date_from: &str, date_to: &str) -> Vec<Period> {
+    let periods = match api.get_periods(date_from, date_to).await {
+        Ok(periods) => periods,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut approved = Vec::new();
+    for period in periods {
+        if let Ok(approval) = api
+            .get_timesheet_approval(&api.config.account_id, &period)
+            .await
+        {
+            if approval.status.is_approved() {
+                approved.push(period);
+            }
+        }
+    }
+
+    approved
+}
+
+// Find the approval period containing the date
+pub async fn find_period(api: &ApiClient, date: &str) -> Result<Period, String> {
+    api.get_periods(date, date)
+        .await?
+        .into_iter()
+        .find(|period| period_contains(period, date))
+        .ok_or_else(|| format!("No approval period found for {}", date))
+}
+
+pub fn period_contains(period: &Period, date: &str) -> bool {
+    period.from.as_str() <= date && date <= period.to.as_str()
+}
+
+async fn reviewer_name(api: &ApiClient, approval: &TimesheetApproval) -> Option<String> {
+    let reviewer = approval.reviewer.as_ref()?;
+
+    Some(user_name(api, &reviewer.account_id).await)
+}
+
+// Display name of a Jira user, or the account ID if it can't be fetched
+pub async fn user_name(api: &ApiClient, account_id: &str) -> String {
+    match api.get_jira_user(account_id).await {
+        Ok(user) => user.display_name,
+        Err(_) => account_id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_period_contains() {
+        let period = Period {
+            from: "2025-04-07".to_string(),
+            to: "2025-04-13".to_string(),
+        };
+
+        assert!(period_contains(&period, "2025-04-07"));
+        assert!(period_contains(&period, "2025-04-13"));
+        assert!(!period_contains(&period, "2025-04-06"));
+        assert!(!period_contains(&period, "2025-04-14"));
+    }
+}
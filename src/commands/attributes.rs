@@ -0,0 +1,39 @@
+use crate::api::ApiClient;
+use crate::models::WorkAttribute;
+
+use spinners::{Spinner, Spinners};
+use tabled::{builder::Builder, settings::Style};
+
+pub async fn list_work_attributes(api: &ApiClient, refresh: bool) {
+    let mut spinner = Spinner::new(Spinners::Dots, "Retrieving work attributes...".to_string());
+
+    match api.get_work_attributes(refresh).await {
+        Ok(attributes) if attributes.is_empty() => {
+            spinner.stop_with_message("\nNo work attributes configured in Tempo".to_string())
+        }
+        Ok(attributes) => {
+            spinner.stop_with_message(format!("\n{}", build_attributes_table(&attributes)))
+        }
+        Err(e) => {
+            spinner.stop_with_message(format!("\nError. Failed to list work attributes: {}", e))
+        }
+    }
+}
+
+fn build_attributes_table(attributes: &[WorkAttribute]) -> String {
+    let mut builder = Builder::default();
+
+    builder.push_record(vec!["Key", "Name", "Type", "Required", "Values"]);
+
+    for attribute in attributes {
+        builder.push_record(vec![
+            attribute.key.clone(),
+            attribute.name.clone(),
+            attribute.attribute_type.clone(),
+            if attribute.required { "yes" } else { "no" }.to_string(),
+            attribute.values.join(", "),
+        ]);
+    }
+
+    builder.build().with(Style::modern()).to_string()
+}
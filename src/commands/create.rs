@@ -0,0 +1,1048 @@
+use crate::api::{is_offline_error, ApiClient, ApiTrait};
+use crate::commands::approvals::approved_periods;
+use crate::commands::journal::record_created;
+use crate::commands::list::{apply_common_formatting, truncate_string};
+use crate::commands::sync::queue_worklogs;
+use crate::models::{NewWorklog, Period, WorkAttribute, WorkAttributeValue, WorklogItem};
+use crate::settings::{BillableSettings, DurationSettings, KeepOriginal, RoundingSettings};
+use crate::storage::Storage;
+use crate::utils::{format_duration, parse_duration_from_string, round_duration};
+use crate::validation::{
+    check_approved_periods, missing_work_attributes, validate_new_worklogs,
+    validate_work_attributes,
+};
+use crate::validators;
+
+use chrono::{Duration, Local, NaiveDate};
+use dialoguer::{Input, Select};
+use spinners::{Spinner, Spinners};
+use tabled::{
+    builder::Builder,
+    settings::object::Rows,
+    settings::{Alignment, Span},
+    Table,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+pub enum DuplicatePolicy {
+    // Don't create worklogs that already exist
+    #[default]
+    Skip,
+    // Create them, but warn about it
+    Warn,
+    // Create them without checking
+    Force,
+}
+
+// Options shared by all commands that create worklogs
+#[derive(Debug, Clone, Copy, Default, clap::Args)]
+pub struct CreateOptions {
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        help = "What to do with worklogs that already exist on that day"
+    )]
+    pub on_duplicate: DuplicatePolicy,
+    #[arg(long, help = "Create worklogs even if they break the validation rules")]
+    pub force: bool,
+    #[arg(long, help = "Don't round the durations")]
+    pub no_round: bool,
+}
+
+impl CreateOptions {
+    // Round the durations according to the settings, unless disabled
+    pub fn round(&self, worklogs: Vec<NewWorklog>, settings: &RoundingSettings) -> Vec<NewWorklog> {
+        if self.no_round {
+            return worklogs;
+        }
+
+        worklogs
+            .into_iter()
+            .map(|worklog| round_worklog(worklog, settings))
+            .collect()
+    }
+
+    // The rounding settings to apply, None when rounding is disabled
+    pub fn rounding<'a>(&self, settings: &'a RoundingSettings) -> Option<&'a RoundingSettings> {
+        (!self.no_round).then_some(settings)
+    }
+}
+
+// Worklog fields given on the command line
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct WorklogArgs {
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = validators::validate_duration,
+        conflicts_with = "non_billable",
+        help = "Billable time, if it differs from the time spent (e.g., 1h)"
+    )]
+    pub billable: Option<String>,
+    #[arg(long, help = "Log the time as non-billable")]
+    pub non_billable: bool,
+    #[arg(
+        long = "attr",
+        value_name = "KEY=VALUE",
+        value_parser = parse_work_attribute,
+        help = "Work attribute to set, by key or name (e.g., _WorkType_=Development)"
+    )]
+    pub attributes: Vec<WorkAttributeValue>,
+    #[arg(
+        long,
+        help = "The Tempo account key. Defaults to the default account of the project"
+    )]
+    pub account: Option<String>,
+}
+
+fn parse_work_attribute(s: &str) -> Result<WorkAttributeValue, String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => Ok(WorkAttributeValue {
+            key: key.trim().to_string(),
+            value: value.trim().to_string(),
+        }),
+        _ => Err(format!(
+            "Invalid work attribute: '{}'. Expected KEY=VALUE",
+            s
+        )),
+    }
+}
+
+impl WorklogArgs {
+    // Billable seconds to log, None if not given
+    pub fn billable_seconds(&self, durations: &DurationSettings) -> Result<Option<i32>, String> {
+        if self.non_billable {
+            return Ok(Some(0));
+        }
+
+        self.billable
+            .as_deref()
+            .map(|billable| parse_duration_from_string(billable, durations))
+            .transpose()
+    }
+
+    // Set the given work attributes over the current ones. The values are checked against
+    // the attribute definitions, and the required attributes must have one
+    pub async fn work_attributes(
+        &self,
+        api: &ApiClient,
+        current: &[WorkAttributeValue],
+    ) -> Result<Vec<WorkAttributeValue>, String> {
+        let definitions = load_work_attributes(api).await?;
+
+        let given = validate_work_attributes(&self.attributes, &definitions)?;
+        let mut attributes: Vec<WorkAttributeValue> = current
+            .iter()
+            .filter(|attribute| !given.iter().any(|g| g.key == attribute.key))
+            .cloned()
+            .collect();
+        attributes.extend(given);
+
+        let missing: Vec<String> = missing_work_attributes(&attributes, &definitions)
+            .iter()
+            .map(|d| format!("{} ({})", d.name, d.key))
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(format!(
+                "Missing required work attribute(s): {}. Set them with --attr KEY=VALUE",
+                missing.join(", ")
+            ));
+        }
+
+        Ok(attributes)
+    }
+}
+
+// Work attribute definitions, needed to check the required attributes
+async fn load_work_attributes(api: &ApiClient) -> Result<Vec<WorkAttribute>, String> {
+    api.get_work_attributes(false)
+        .await
+        .map_err(|e| format!("Failed to load the work attributes: {}", e))
+}
+
+// Describe the worklogs lacking a required work attribute
+fn find_missing_work_attributes(
+    worklogs: &[NewWorklog],
+    definitions: &[WorkAttribute],
+) -> Vec<String> {
+    worklogs
+        .iter()
+        .filter_map(|worklog| {
+            let missing: Vec<String> = missing_work_attributes(&worklog.attributes, definitions)
+                .iter()
+                .map(|d| format!("{} ({})", d.name, d.key))
+                .collect();
+
+            (!missing.is_empty()).then(|| {
+                format!(
+                    "{} on {} is missing required work attribute(s): {}",
+                    worklog.issue_key,
+                    worklog.start_date,
+                    missing.join(", ")
+                )
+            })
+        })
+        .collect()
+}
+
+// Expand aliases, which bring their billable default, and mark worklogs on non-billable
+// projects as such, unless their billable time is already set
+pub fn apply_billable_policy(
+    worklogs: Vec<NewWorklog>,
+    storage: &Storage,
+    settings: &BillableSettings,
+) -> Vec<NewWorklog> {
+    worklogs
+        .into_iter()
+        .map(|mut worklog| {
+            if let Some(alias) = storage.get_alias(&worklog.issue_key) {
+                worklog.issue_key = alias.issue_key;
+                worklog.billable_seconds = worklog.billable_seconds.or(match alias.billable {
+                    Some(true) => Some(worklog.time_spent_seconds),
+                    Some(false) => Some(0),
+                    None => None,
+                });
+            }
+
+            if worklog.billable_seconds.is_none() && !settings.is_billable(&worklog.issue_key) {
+                worklog.billable_seconds = Some(0);
+            }
+            worklog
+        })
+        .collect()
+}
+
+#[derive(Debug, Default)]
+pub struct CreateReport {
+    pub created: Vec<(NewWorklog, WorklogItem)>,
+    pub warnings: Vec<String>,
+}
+
+// Error of a batch creation. `created` holds the worklogs that couldn't be rolled back because
+// the network went down, they stay in Tempo and only the rest of the batch is left to log
+#[derive(Debug, Default)]
+pub struct CreateError {
+    pub message: String,
+    pub created: Vec<(NewWorklog, WorklogItem)>,
+}
+
+impl CreateError {
+    // The worklogs of the batch that weren't created, by index
+    pub fn not_created(&self, worklogs: &[NewWorklog]) -> Vec<usize> {
+        let mut created: Vec<&NewWorklog> = self.created.iter().map(|(w, _)| w).collect();
+
+        (0..worklogs.len())
+            .filter(|&i| match created.iter().position(|w| **w == worklogs[i]) {
+                Some(position) => {
+                    created.remove(position);
+                    false
+                }
+                None => true,
+            })
+            .collect()
+    }
+}
+
+impl From<String> for CreateError {
+    fn from(message: String) -> Self {
+        CreateError {
+            message,
+            created: Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for CreateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+// Create worklogs one by one. Issues are resolved upfront, duplicates of existing worklogs
+// are handled by the `on_duplicate` policy, and the worklogs left are checked against the
+// validation rules. If any post fails, the worklogs created so far are deleted, so the batch
+// is either logged completely or not at all
+pub async fn create_worklogs(
+    api: &ApiClient,
+    worklogs: &[NewWorklog],
+    options: &CreateOptions,
+) -> Result<CreateReport, CreateError> {
+    let issue_keys: Vec<String> = worklogs.iter().map(|w| w.issue_key.clone()).collect();
+    let issues = api.get_jira_issues_concurrently(&issue_keys).await?;
+
+    // Tempo rejects these anyway, better before anything is created
+    let missing = find_missing_work_attributes(worklogs, &load_work_attributes(api).await?);
+    if !missing.is_empty() {
+        return Err(missing.join("\n").into());
+    }
+
+    let existing = if options.on_duplicate == DuplicatePolicy::Force && options.force {
+        Vec::new()
+    } else {
+        fetch_existing_worklogs(api, worklogs).await?
+    };
+
+    let mut report = CreateReport::default();
+    let mut to_post = Vec::new();
+
+    for worklog in worklogs {
+        let issue = &issues[&worklog.issue_key];
+
+        let duplicate = match options.on_duplicate {
+            DuplicatePolicy::Force => None,
+            _ => find_duplicate(worklog, &issue.id, &existing),
+        };
+
+        if let Some(duplicate) = duplicate {
+            let description = format!(
+                "{} {} {} \"{}\"",
+                worklog.start_date,
+                issue.key,
+                format_duration(worklog.time_spent_seconds),
+                worklog.description
+            );
+
+            if options.on_duplicate == DuplicatePolicy::Skip {
+                report.warnings.push(format!(
+                    "Skipped {}, it duplicates worklog {}",
+                    description, duplicate.tempo_worklog_id
+                ));
+                continue;
+            }
+
+            report.warnings.push(format!(
+                "Created {}, but it duplicates worklog {}",
+                description, duplicate.tempo_worklog_id
+            ));
+        }
+
+        to_post.push(worklog.clone());
+    }
+
+    // Only the worklogs actually posted are validated, a skipped duplicate would overlap its
+    // existing copy
+    if !options.force {
+        let today = Local::now().date_naive();
+        let mut violations =
+            validate_new_worklogs(&to_post, &existing, &api.settings.validation, today);
+        violations.extend(check_approved_periods(
+            &to_post,
+            &[],
+            &fetch_approved_periods(api, &to_post).await,
+        ));
+
+        if !violations.is_empty() {
+            return Err(format!(
+                "Validation failed, use --force to log anyway:\n  {}",
+                violations.join("\n  ")
+            )
+            .into());
+        }
+    }
+
+    for worklog in &to_post {
+        match api.log_time(worklog).await {
+            Ok(item) => report.created.push((worklog.clone(), item)),
+            Err(e) => {
+                let error = format!("Failed to log time on {}: {}", worklog.issue_key, e);
+                return Err(rollback(api, report.created, error).await);
+            }
+        }
+    }
+
+    let now = Local::now().timestamp();
+    for (worklog, _) in &report.created {
+        api.storage.store_recent_issue(&worklog.issue_key, now);
+    }
+    record_created(&api.storage, &report.created);
+
+    Ok(report)
+}
+
+// Fetch the approved timesheet periods the new worklogs could fall into
+async fn fetch_approved_periods(api: &ApiClient, worklogs: &[NewWorklog]) -> Vec<Period> {
+    let date_from = worklogs.iter().map(|w| &w.start_date).min();
+    let date_to = worklogs.iter().map(|w| &w.start_date).max();
+
+    match (date_from, date_to) {
+        (Some(date_from), Some(date_to)) => approved_periods(api, date_from, date_to).await,
+        _ => Vec::new(),
+    }
+}
+
+// Fetch the worklogs of all the days the new worklogs are logged on, and the days around
+// them for the worklogs running past midnight
+pub async fn fetch_existing_worklogs(
+    api: &ApiClient,
+    worklogs: &[NewWorklog],
+) -> Result<Vec<WorklogItem>, String> {
+    let dates: Vec<NaiveDate> = worklogs
+        .iter()
+        .filter_map(|w| NaiveDate::parse_from_str(&w.start_date, "%Y-%m-%d").ok())
+        .collect();
+
+    match (dates.iter().min(), dates.iter().max()) {
+        (Some(date_from), Some(date_to)) => {
+            let date_from = (*date_from - Duration::days(1)).format("%Y-%m-%d");
+            let date_to = (*date_to + Duration::days(1)).format("%Y-%m-%d");
+            api.list_worklogs(&date_from.to_string(), &date_to.to_string())
+                .await
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+// Round the duration and keep the original one where the settings say so
+fn round_worklog(mut worklog: NewWorklog, settings: &RoundingSettings) -> NewWorklog {
+    let original = worklog.time_spent_seconds;
+    worklog.time_spent_seconds = round_duration(original, settings);
+
+    // Billable time that follows the time spent is rounded along with it
+    if worklog.billable_seconds == Some(original) {
+        worklog.billable_seconds = Some(worklog.time_spent_seconds);
+    }
+
+    keep_original_duration(&mut worklog, original, settings);
+    worklog
+}
+
+// Keep the duration from before rounding in the description or an attribute, if it changed
+pub fn keep_original_duration(
+    worklog: &mut NewWorklog,
+    original: i32,
+    settings: &RoundingSettings,
+) {
+    if worklog.time_spent_seconds == original {
+        return;
+    }
+
+    match settings.keep_original {
+        KeepOriginal::None => {}
+        KeepOriginal::Description if worklog.description.is_empty() => {
+            worklog.description = format!("Original: {}", format_duration(original));
+        }
+        KeepOriginal::Description => {
+            worklog.description = format!(
+                "{} (original: {})",
+                worklog.description,
+                format_duration(original)
+            );
+        }
+        KeepOriginal::Attribute => worklog.attributes.push(WorkAttributeValue {
+            key: settings.attribute.clone(),
+            value: format_duration(original),
+        }),
+    }
+}
+
+// Find an existing worklog with the same issue, date, duration and description.
+// The start time is compared only when the new worklog has one
+fn find_duplicate<'a>(
+    worklog: &NewWorklog,
+    issue_id: &str,
+    existing: &'a [WorklogItem],
+) -> Option<&'a WorklogItem> {
+    existing.iter().find(|item| {
+        item.issue.id.to_string() == issue_id
+            && item.start_date == worklog.start_date
+            && item.time_spent_seconds == worklog.time_spent_seconds
+            && item.description == worklog.description
+            && worklog
+                .start_time
+                .as_ref()
+                .map_or(true, |start_time| *start_time == item.start_time)
+    })
+}
+
+// Delete already created worklogs and describe the outcome in the error message. Without
+// network the worklogs are kept and returned, so that only the rest of the batch gets queued
+async fn rollback(
+    api: &ApiClient,
+    created: Vec<(NewWorklog, WorklogItem)>,
+    error: String,
+) -> CreateError {
+    if created.is_empty() {
+        return error.into();
+    }
+
+    let ids: Vec<String> = created
+        .iter()
+        .map(|(_, item)| item.tempo_worklog_id.to_string())
+        .collect();
+
+    match api.delete_worklogs(&ids).await {
+        Ok(_) => format!("{}. Rolled back worklog(s): {}", error, ids.join(", ")).into(),
+        Err(e) if is_offline_error(&e) => {
+            let now = Local::now().timestamp();
+            for (worklog, _) in &created {
+                api.storage.store_recent_issue(&worklog.issue_key, now);
+            }
+            record_created(&api.storage, &created);
+
+            CreateError {
+                message: format!(
+                    "{}. Rollback failed without network, kept worklog(s): {}",
+                    error,
+                    ids.join(", ")
+                ),
+                created,
+            }
+        }
+        Err(e) => format!(
+            "{}. Rollback failed ({}), please delete worklog(s) manually: {}",
+            error,
+            e,
+            ids.join(", ")
+        )
+        .into(),
+    }
+}
+
+// Let the user review the worklogs, unless `yes` is set, and create them
+pub async fn review_and_create_worklogs(
+    api: &ApiClient,
+    worklogs: Vec<NewWorklog>,
+    yes: bool,
+    options: &CreateOptions,
+) {
+    let worklogs = options.round(worklogs, &api.settings.rounding);
+    let worklogs = apply_billable_policy(worklogs, &api.storage, &api.settings.billable);
+    let worklogs = if yes {
+        println!("{}", build_preview_table(&worklogs));
+        worklogs
+    } else {
+        let rounding = options.rounding(&api.settings.rounding);
+        match review_worklogs(worklogs, &api.settings.durations, rounding) {
+            Some(worklogs) => worklogs,
+            None => {
+                println!("Cancelled, no worklogs created");
+                return;
+            }
+        }
+    };
+
+    let mut spinner = Spinner::new(Spinners::Dots, "Logging time...".to_string());
+
+    match create_worklogs(api, &worklogs, options).await {
+        Ok(report) => {
+            spinner.stop_with_message(format!(
+                "\n{} worklog(s) created successfully",
+                report.created.len()
+            ));
+            print_warnings(&report.warnings);
+        }
+        Err(e) if is_offline_error(&e.message) => {
+            let left: Vec<NewWorklog> = e
+                .not_created(&worklogs)
+                .into_iter()
+                .map(|i| worklogs[i].clone())
+                .collect();
+            spinner.stop_with_message(format!(
+                "\n{}\n{}",
+                e,
+                queue_worklogs(&api.storage, &left, None, options.force)
+            ));
+        }
+        Err(e) => spinner.stop_with_message(format!("\nError. Failed to log time: {}", e)),
+    }
+}
+
+pub fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        println!("Warning. {}", warning);
+    }
+}
+
+// Show the worklogs and let the user create, edit or drop them. Returns None if cancelled
+fn review_worklogs(
+    mut worklogs: Vec<NewWorklog>,
+    durations: &DurationSettings,
+    rounding: Option<&RoundingSettings>,
+) -> Option<Vec<NewWorklog>> {
+    loop {
+        println!("{}", build_preview_table(&worklogs));
+
+        if worklogs.is_empty() {
+            return None;
+        }
+
+        let choice = Select::new()
+            .with_prompt("What do you want to do?")
+            .items(&["Create worklogs", "Edit", "Cancel"])
+            .default(0)
+            .interact()
+            .unwrap();
+
+        match choice {
+            0 => return Some(worklogs),
+            1 => worklogs = edit_worklogs(worklogs, durations, rounding),
+            _ => return None,
+        }
+    }
+}
+
+// Ask for a new duration and description of each worklog. Zero duration drops the worklog,
+// changed durations are rounded like the others
+fn edit_worklogs(
+    worklogs: Vec<NewWorklog>,
+    durations: &DurationSettings,
+    rounding: Option<&RoundingSettings>,
+) -> Vec<NewWorklog> {
+    worklogs
+        .into_iter()
+        .filter_map(|mut worklog| {
+            let duration: String = Input::new()
+                .with_prompt(format!(
+                    "{} {} duration (0 to drop)",
+                    worklog.start_date, worklog.issue_key
+                ))
+                .default(format_duration(worklog.time_spent_seconds))
+                .validate_with(|input: &String| {
+                    parse_duration_from_string(input, durations).map(|_| ())
+                })
+                .interact_text()
+                .unwrap();
+
+            let seconds = parse_duration_from_string(&duration, durations).ok()?;
+
+            if seconds == 0 {
+                return None;
+            }
+
+            if seconds != worklog.time_spent_seconds {
+                worklog.time_spent_seconds = match rounding {
+                    Some(rounding) => round_duration(seconds, rounding),
+                    None => seconds,
+                };
+            }
+
+            worklog.description = Input::new()
+                .with_prompt("Description")
+                .default(worklog.description)
+                .allow_empty(true)
+                .interact_text()
+                .unwrap();
+
+            Some(worklog)
+        })
+        .collect()
+}
+
+// Build a table to preview worklogs before they're created
+fn build_preview_table(worklogs: &[NewWorklog]) -> Table {
+    let mut builder = Builder::default();
+
+    builder.push_record(vec!["Date", "Issue", "Duration", "Billable", "Description"]);
+
+    for worklog in worklogs {
+        let date = match &worklog.start_time {
+            Some(start_time) => format!(
+                "{} {}",
+                worklog.start_date,
+                start_time.get(..5).unwrap_or(start_time)
+            ),
+            None => worklog.start_date.clone(),
+        };
+
+        builder.push_record(vec![
+            date,
+            worklog.issue_key.clone(),
+            format_duration(worklog.time_spent_seconds),
+            format_duration(
+                worklog
+                    .billable_seconds
+                    .unwrap_or(worklog.time_spent_seconds),
+            ),
+            truncate_string(&worklog.description, 100),
+        ]);
+    }
+
+    let total_time: i32 = worklogs.iter().map(|w| w.time_spent_seconds).sum();
+    builder.push_record(vec![format!(
+        "{} worklog(s), {}",
+        worklogs.len(),
+        format_duration(total_time)
+    )]);
+
+    let mut table = builder.build();
+    table.modify(Rows::last(), Span::column(5));
+    table.modify(Rows::last(), Alignment::right());
+    apply_common_formatting(&mut table);
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::create_test_api;
+    use crate::models::Alias;
+    use crate::models::{TempoIssue, WorklogAttributes};
+    use crate::settings::RoundingMode;
+    use mockito::Matcher;
+
+    fn worklog_item(id: i64, issue_id: i64, start_time: &str, description: &str) -> WorklogItem {
+        WorklogItem {
+            tempo_worklog_id: id,
+            time_spent_seconds: 3600,
+            billable_seconds: 3600,
+            description: description.to_string(),
+            start_date: "2025-04-07".to_string(),
+            start_time: start_time.to_string(),
+            created_at: "2025-04-07T10:00:00Z".to_string(),
+            updated_at: String::new(),
+            issue: TempoIssue { id: issue_id },
+            author: None,
+            attributes: WorklogAttributes::default(),
+            jira_issue: None,
+        }
+    }
+
+    #[test]
+    fn test_not_created() {
+        let worklog = |issue_key: &str| NewWorklog {
+            issue_key: issue_key.to_string(),
+            time_spent_seconds: 3600,
+            description: "Sync".to_string(),
+            start_date: "2025-04-07".to_string(),
+            start_time: None,
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        };
+        let batch = vec![worklog("ABC-1"), worklog("ABC-1"), worklog("ABC-2")];
+
+        // One of two identical worklogs was created before the network went down
+        let error = CreateError {
+            message: "Network unavailable".to_string(),
+            created: vec![(worklog("ABC-1"), worklog_item(1, 100, "09:00:00", "Sync"))],
+        };
+        assert_eq!(error.not_created(&batch), vec![1, 2]);
+
+        let error = CreateError::from("Network unavailable".to_string());
+        assert_eq!(error.not_created(&batch), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_find_duplicate() {
+        let existing = vec![
+            worklog_item(1, 100, "09:00:00", "Sync"),
+            worklog_item(2, 200, "10:00:00", "Sync"),
+        ];
+        let worklog = NewWorklog {
+            issue_key: "ABC-1".to_string(),
+            time_spent_seconds: 3600,
+            description: "Sync".to_string(),
+            start_date: "2025-04-07".to_string(),
+            start_time: None,
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        };
+
+        let duplicate = find_duplicate(&worklog, "200", &existing);
+        assert_eq!(duplicate.map(|w| w.tempo_worklog_id), Some(2));
+
+        let with_start_time = NewWorklog {
+            start_time: Some("10:00:00".to_string()),
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+            ..worklog.clone()
+        };
+        assert!(find_duplicate(&with_start_time, "200", &existing).is_some());
+        assert!(find_duplicate(&with_start_time, "100", &existing).is_none());
+
+        assert!(find_duplicate(&worklog, "300", &existing).is_none());
+        assert!(find_duplicate(
+            &NewWorklog {
+                description: "Other".to_string(),
+                ..worklog.clone()
+            },
+            "200",
+            &existing
+        )
+        .is_none());
+        assert!(find_duplicate(
+            &NewWorklog {
+                time_spent_seconds: 1800,
+                ..worklog.clone()
+            },
+            "200",
+            &existing
+        )
+        .is_none());
+        assert!(find_duplicate(
+            &NewWorklog {
+                start_date: "2025-04-08".to_string(),
+                ..worklog.clone()
+            },
+            "200",
+            &existing
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_round_worklog() {
+        let worklog = NewWorklog {
+            issue_key: "ABC-1".to_string(),
+            time_spent_seconds: 47 * 60,
+            description: "Review".to_string(),
+            start_date: "2025-04-07".to_string(),
+            start_time: None,
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        };
+        let settings = |keep_original| RoundingSettings {
+            mode: RoundingMode::Nearest,
+            keep_original,
+            ..RoundingSettings::default()
+        };
+
+        let rounded = round_worklog(worklog.clone(), &settings(KeepOriginal::None));
+        assert_eq!(rounded.time_spent_seconds, 2700);
+        assert_eq!(rounded.description, "Review");
+        assert!(rounded.attributes.is_empty());
+
+        let rounded = round_worklog(worklog.clone(), &settings(KeepOriginal::Description));
+        assert_eq!(rounded.description, "Review (original: 47m)");
+
+        let rounded = round_worklog(worklog.clone(), &settings(KeepOriginal::Attribute));
+        assert_eq!(rounded.description, "Review");
+        assert_eq!(
+            rounded.attributes,
+            vec![WorkAttributeValue {
+                key: "_OriginalDuration_".to_string(),
+                value: "47m".to_string(),
+            }]
+        );
+
+        // Nothing is kept when the duration doesn't change
+        let exact = NewWorklog {
+            time_spent_seconds: 2700,
+            ..worklog.clone()
+        };
+        let rounded = round_worklog(exact, &settings(KeepOriginal::Description));
+        assert_eq!(rounded.description, "Review");
+
+        let options = CreateOptions {
+            no_round: true,
+            ..CreateOptions::default()
+        };
+        let worklogs = options.round(vec![worklog], &settings(KeepOriginal::None));
+        assert_eq!(worklogs[0].time_spent_seconds, 47 * 60);
+    }
+
+    #[test]
+    fn test_parse_work_attribute() {
+        assert_eq!(
+            parse_work_attribute("_WorkType_=Development"),
+            Ok(WorkAttributeValue {
+                key: "_WorkType_".to_string(),
+                value: "Development".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_work_attribute("Work type = Code review").map(|a| (a.key, a.value)),
+            Ok(("Work type".to_string(), "Code review".to_string()))
+        );
+        assert!(parse_work_attribute("Development").is_err());
+        assert!(parse_work_attribute("=Development").is_err());
+    }
+
+    #[test]
+    fn test_find_missing_work_attributes() {
+        let definitions = vec![WorkAttribute {
+            key: "_WorkType_".to_string(),
+            name: "Work type".to_string(),
+            attribute_type: "STATIC_LIST".to_string(),
+            required: true,
+            values: vec!["Development".to_string()],
+        }];
+        let worklog = |attributes: Vec<WorkAttributeValue>| NewWorklog {
+            issue_key: "ABC-1".to_string(),
+            time_spent_seconds: 3600,
+            description: String::new(),
+            start_date: "2025-04-07".to_string(),
+            start_time: None,
+            billable_seconds: None,
+            attributes,
+            remaining_estimate_seconds: None,
+        };
+        let work_type = WorkAttributeValue {
+            key: "_WorkType_".to_string(),
+            value: "Development".to_string(),
+        };
+
+        assert_eq!(
+            find_missing_work_attributes(
+                &[worklog(vec![work_type]), worklog(Vec::new())],
+                &definitions
+            ),
+            vec![
+                "ABC-1 on 2025-04-07 is missing required work attribute(s): Work type (_WorkType_)"
+            ]
+        );
+        assert!(find_missing_work_attributes(&[worklog(Vec::new())], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_apply_billable_policy() {
+        let worklog = |issue_key: &str, billable_seconds| NewWorklog {
+            issue_key: issue_key.to_string(),
+            time_spent_seconds: 3600,
+            description: String::new(),
+            start_date: "2025-04-07".to_string(),
+            start_time: None,
+            billable_seconds,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        };
+        let settings = BillableSettings {
+            default: true,
+            projects: [("INTERNAL".to_string(), false)].into(),
+        };
+        let test_db_path = "test_apply_billable_policy";
+        let _ = std::fs::remove_dir_all(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+        let alias = |name: &str, issue_key: &str, billable| Alias {
+            name: name.to_string(),
+            issue_key: issue_key.to_string(),
+            description: None,
+            duration: None,
+            billable,
+        };
+        storage.store_alias(&alias("standup", "INTERNAL-4821", None));
+        storage.store_alias(&alias("support", "INTERNAL-7", Some(true)));
+        storage.store_alias(&alias("reading", "ABC-9", Some(false)));
+
+        let worklogs = apply_billable_policy(
+            vec![
+                worklog("ABC-1", None),
+                worklog("INTERNAL-1", None),
+                worklog("INTERNAL-2", Some(1800)),
+                worklog("standup", None),
+                worklog("support", None),
+                worklog("reading", None),
+                worklog("reading", Some(1800)),
+            ],
+            &storage,
+            &settings,
+        );
+
+        let billable: Vec<(&str, Option<i32>)> = worklogs
+            .iter()
+            .map(|w| (w.issue_key.as_str(), w.billable_seconds))
+            .collect();
+        assert_eq!(
+            billable,
+            vec![
+                ("ABC-1", None),
+                ("INTERNAL-1", Some(0)),
+                ("INTERNAL-2", Some(1800)),
+                // The project policy applies to the issue the alias points to
+                ("INTERNAL-4821", Some(0)),
+                ("INTERNAL-7", Some(3600)),
+                ("ABC-9", Some(0)),
+                ("ABC-9", Some(1800)),
+            ]
+        );
+
+        drop(storage);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    #[test]
+    fn test_build_preview_table() {
+        let worklogs = vec![
+            NewWorklog {
+                issue_key: "ABC-1".to_string(),
+                time_spent_seconds: 5400,
+                description: "Some work".to_string(),
+                start_date: "2025-04-07".to_string(),
+                start_time: Some("10:00:00".to_string()),
+                billable_seconds: None,
+                attributes: Vec::new(),
+                remaining_estimate_seconds: None,
+            },
+            NewWorklog {
+                issue_key: "ABC-2".to_string(),
+                time_spent_seconds: 1800,
+                description: "Other work".to_string(),
+                start_date: "2025-04-08".to_string(),
+                start_time: None,
+                billable_seconds: None,
+                attributes: Vec::new(),
+                remaining_estimate_seconds: None,
+            },
+        ];
+
+        let table = build_preview_table(&worklogs).to_string();
+
+        assert!(table.contains("Date"));
+        assert!(table.contains("2025-04-07 10:00"));
+        assert!(table.contains("ABC-1"));
+        assert!(table.contains("1h30m"));
+        assert!(table.contains("Some work"));
+        assert!(table.contains("2025-04-08"));
+        assert!(table.contains("ABC-2"));
+        assert!(table.contains("30m"));
+        assert!(table.contains("2 worklog(s), 2h"));
+    }
+
+    #[tokio::test]
+    async fn test_create_skips_duplicate_with_start_time() {
+        let test_db_path = "test_create_skips_duplicate";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_test_api(test_db_path, &server.url());
+
+        server
+            .mock("GET", "/work-attributes")
+            .with_body(r#"{"results": []}"#)
+            .create_async()
+            .await;
+        // The same worklog, created by an earlier import
+        server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(Matcher::Any)
+            .with_body(
+                r#"{"results": [{"tempoWorklogId": 7, "timeSpentSeconds": 3600,
+                    "description": "Sync", "startDate": "2025-04-07", "startTime": "09:00:00",
+                    "createdAt": "2025-04-07T10:00:00Z", "issue": {"id": 10001}}]}"#,
+            )
+            .create_async()
+            .await;
+        let post = server
+            .mock("POST", "/worklogs/")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let worklog = NewWorklog {
+            issue_key: "ABC-1".to_string(),
+            time_spent_seconds: 3600,
+            description: "Sync".to_string(),
+            start_date: "2025-04-07".to_string(),
+            start_time: Some("09:00:00".to_string()),
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        };
+
+        // It would overlap its existing copy, but it's skipped before the validation
+        let report = create_worklogs(&api, &[worklog], &CreateOptions::default())
+            .await
+            .unwrap();
+        assert!(report.created.is_empty());
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("duplicates worklog 7"));
+        post.assert_async().await;
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+}
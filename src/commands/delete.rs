@@ -1,10 +1,15 @@
-use crate::api::{ApiClient, ApiTrait};
+use crate::api::{is_offline_error, ApiClient, ApiTrait};
+use crate::commands::approvals::approved_periods;
+use crate::commands::journal::record_deleted;
+use crate::commands::sync::queue_change;
+use crate::models::{NewWorklog, PendingChange, WorklogItem};
+use crate::validation::check_approved_periods;
 use spinners::{Spinner, Spinners};
 
-pub async fn delete_log(api: &ApiClient, worklog_ids: &Vec<String>) {
+pub async fn delete_log(api: &ApiClient, worklog_ids: &Vec<String>, force: bool) {
     let mut spinner = Spinner::new(Spinners::Dots, "Deleting worklog...".to_string());
 
-    match api.delete_worklogs(worklog_ids).await {
+    match delete_and_record(api, worklog_ids, force).await {
         Ok(_) => spinner.stop_with_message(format!(
             "Worklog(s) deleted successfully: {}",
             worklog_ids.join(", ")
@@ -12,3 +17,70 @@ pub async fn delete_log(api: &ApiClient, worklog_ids: &Vec<String>) {
         Err(e) => spinner.stop_with_message(format!("Error. {}", e)),
     }
 }
+
+// Delete the worklogs one by one, keeping a copy of each in the journal so it can be undone.
+// Without network, the worklogs left are queued
+async fn delete_and_record(
+    api: &ApiClient,
+    worklog_ids: &[String],
+    force: bool,
+) -> Result<(), String> {
+    for (index, worklog_id) in worklog_ids.iter().enumerate() {
+        match delete_one(api, worklog_id, force).await {
+            Ok(_) => {}
+            Err(e) if is_offline_error(&e) => {
+                let queued = &worklog_ids[index..];
+                for worklog_id in queued {
+                    queue_change(
+                        &api.storage,
+                        PendingChange::Delete {
+                            worklog_id: worklog_id.clone(),
+                        },
+                    );
+                }
+
+                return Err(format!(
+                    "Network unavailable, the deletion of worklog(s) {} is queued. Run 'tempie sync' to apply it",
+                    queued.join(", ")
+                ));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+async fn delete_one(api: &ApiClient, worklog_id: &str, force: bool) -> Result<(), String> {
+    let worklog = api.get_worklog(worklog_id).await?;
+
+    if !force {
+        check_deletable(api, &worklog).await?;
+    }
+
+    api.delete_worklogs(&vec![worklog_id.to_string()]).await?;
+    record_deleted(
+        &api.storage,
+        worklog_id,
+        NewWorklog::from_worklog_item(&worklog),
+    );
+
+    Ok(())
+}
+
+// Worklogs in approved periods are only deleted with --force
+pub async fn check_deletable(api: &ApiClient, worklog: &WorklogItem) -> Result<(), String> {
+    let violations = check_approved_periods(
+        &[],
+        std::slice::from_ref(worklog),
+        &approved_periods(api, &worklog.start_date, &worklog.start_date).await,
+    );
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Validation failed, use --force to delete anyway:\n  {}",
+            violations.join("\n  ")
+        ))
+    }
+}
@@ -0,0 +1,307 @@
+use crate::api::{is_offline_error, ApiClient, ApiTrait};
+use crate::commands::accounts::{account_attribute, resolve_account};
+use crate::commands::approvals::approved_periods;
+use crate::commands::create::{fetch_existing_worklogs, WorklogArgs};
+use crate::commands::journal::record_edited;
+use crate::commands::sync::queue_change;
+use crate::models::{NewWorklog, PendingChange, WorklogChanges, WorklogItem};
+use crate::settings::DurationSettings;
+use crate::utils::{format_duration, parse_duration_from_string};
+use crate::validation::{check_approved_periods, validate_new_worklogs};
+
+use chrono::{Local, NaiveTime};
+use spinners::{Spinner, Spinners};
+
+pub async fn edit_worklog(
+    api: &ApiClient,
+    worklog_id: &str,
+    changes: &WorklogChanges,
+    worklog_args: &WorklogArgs,
+    force: bool,
+) {
+    let mut spinner = Spinner::new(Spinners::Dots, "Updating worklog...".to_string());
+
+    match update_worklog(api, worklog_id, changes, worklog_args, force).await {
+        Ok(worklog) => spinner.stop_with_message(format!(
+            "\nWorklog {} updated: {} on {}, billable {}",
+            worklog_id,
+            format_duration(worklog.time_spent_seconds),
+            worklog.start_date,
+            format_duration(worklog.billable_seconds)
+        )),
+        Err(e) if is_offline_error(&e) => {
+            queue_change(
+                &api.storage,
+                PendingChange::Edit {
+                    worklog_id: worklog_id.to_string(),
+                    changes: changes.clone(),
+                    billable: worklog_args.billable.clone(),
+                    non_billable: worklog_args.non_billable,
+                    attributes: worklog_args.attributes.clone(),
+                    account: worklog_args.account.clone(),
+                    force,
+                },
+            );
+            spinner.stop_with_message(format!(
+                "\nNetwork unavailable, the edit of worklog {} is queued. Run 'tempie sync' to apply it",
+                worklog_id
+            ));
+        }
+        Err(e) => spinner.stop_with_message(format!("\nError. {}", e)),
+    }
+}
+
+pub async fn update_worklog(
+    api: &ApiClient,
+    worklog_id: &str,
+    changes: &WorklogChanges,
+    worklog_args: &WorklogArgs,
+    force: bool,
+) -> Result<WorklogItem, String> {
+    let worklog = api.get_worklog(worklog_id).await?;
+    let billable_seconds = worklog_args.billable_seconds(&api.settings.durations)?;
+    let mut updated = apply_changes(&worklog, changes, billable_seconds, &api.settings.durations)?;
+    let mut current = worklog.attributes.values.clone();
+    if let Some(account_key) = &worklog_args.account {
+        if let Some(account) = resolve_account(api, &updated.issue_key, Some(account_key)).await? {
+            let account = account_attribute(&account);
+            current.retain(|attribute| attribute.key != account.key);
+            current.push(account);
+        }
+    }
+    updated.attributes = worklog_args.work_attributes(api, &current).await?;
+
+    if !force {
+        // The worklog is checked against the days around it, like new ones, without its
+        // current version
+        let existing: Vec<WorklogItem> =
+            fetch_existing_worklogs(api, std::slice::from_ref(&updated))
+                .await?
+                .into_iter()
+                .filter(|item| item.tempo_worklog_id != worklog.tempo_worklog_id)
+                .collect();
+
+        let today = Local::now().date_naive();
+        let mut violations = validate_new_worklogs(
+            std::slice::from_ref(&updated),
+            &existing,
+            &api.settings.validation,
+            today,
+        );
+
+        // A worklog moved to another date can't leave an approved period either
+        let moved: &[WorklogItem] = if updated.start_date != worklog.start_date {
+            std::slice::from_ref(&worklog)
+        } else {
+            &[]
+        };
+        let date_from = updated.start_date.as_str().min(worklog.start_date.as_str());
+        let date_to = updated.start_date.as_str().max(worklog.start_date.as_str());
+        violations.extend(check_approved_periods(
+            std::slice::from_ref(&updated),
+            moved,
+            &approved_periods(api, date_from, date_to).await,
+        ));
+
+        if !violations.is_empty() {
+            return Err(format!(
+                "Validation failed, use --force to update anyway:\n  {}",
+                violations.join("\n  ")
+            ));
+        }
+    }
+
+    let item = api.update_worklog(worklog_id, &updated).await?;
+    record_edited(
+        &api.storage,
+        worklog_id,
+        NewWorklog::from_worklog_item(&worklog),
+        updated,
+    );
+
+    Ok(item)
+}
+
+// Build the updated worklog. Billable time that followed the time spent keeps following it
+fn apply_changes(
+    worklog: &WorklogItem,
+    changes: &WorklogChanges,
+    billable_seconds: Option<i32>,
+    durations: &DurationSettings,
+) -> Result<NewWorklog, String> {
+    let time_spent_seconds = match &changes.time_spent {
+        Some(time_spent) => parse_duration_from_string(time_spent, durations)?,
+        None => worklog.time_spent_seconds,
+    };
+
+    let billable_seconds = match billable_seconds {
+        Some(billable_seconds) => billable_seconds,
+        None if worklog.billable_seconds == worklog.time_spent_seconds => time_spent_seconds,
+        None => worklog.billable_seconds,
+    };
+
+    let start_time = match &changes.start_time {
+        Some(start_time) => Some(parse_start_time(start_time)?),
+        None if worklog.start_time.is_empty() => None,
+        None => Some(worklog.start_time.clone()),
+    };
+
+    Ok(NewWorklog {
+        issue_key: match &worklog.jira_issue {
+            Some(issue) => issue.key.clone(),
+            None => worklog.issue.id.to_string(),
+        },
+        time_spent_seconds,
+        description: changes
+            .comment
+            .clone()
+            .unwrap_or_else(|| worklog.description.clone()),
+        start_date: changes
+            .date
+            .clone()
+            .unwrap_or_else(|| worklog.start_date.clone()),
+        start_time,
+        billable_seconds: Some(billable_seconds),
+        attributes: worklog.attributes.values.clone(),
+        remaining_estimate_seconds: None,
+    })
+}
+
+// Parse a start time given as HH:MM or HH:MM:SS
+fn parse_start_time(start_time: &str) -> Result<String, String> {
+    NaiveTime::parse_from_str(start_time, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(start_time, "%H:%M"))
+        .map(|time| time.format("%H:%M:%S").to_string())
+        .map_err(|_| format!("Invalid start time: '{}'. Expected HH:MM", start_time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::create_test_api;
+    use crate::models::{JiraIssue, TempoIssue, WorkAttributeValue, WorklogAttributes};
+    use mockito::Matcher;
+
+    fn worklog_item(billable_seconds: i32) -> WorklogItem {
+        WorklogItem {
+            tempo_worklog_id: 99,
+            time_spent_seconds: 3600,
+            billable_seconds,
+            description: "Review".to_string(),
+            start_date: "2025-04-07".to_string(),
+            start_time: "09:00:00".to_string(),
+            created_at: "2025-04-07T10:00:00Z".to_string(),
+            updated_at: String::new(),
+            issue: TempoIssue { id: 123 },
+            author: None,
+            attributes: WorklogAttributes {
+                values: vec![WorkAttributeValue {
+                    key: "_Activity_".to_string(),
+                    value: "Review".to_string(),
+                }],
+            },
+            jira_issue: Some(JiraIssue {
+                id: "123".to_string(),
+                key: "ABC-1".to_string(),
+                fields: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_apply_changes() {
+        let durations = DurationSettings::default();
+
+        let unchanged = apply_changes(
+            &worklog_item(3600),
+            &WorklogChanges::default(),
+            None,
+            &durations,
+        )
+        .unwrap();
+        assert_eq!(unchanged.issue_key, "ABC-1");
+        assert_eq!(unchanged.time_spent_seconds, 3600);
+        assert_eq!(unchanged.billable_seconds, Some(3600));
+        assert_eq!(unchanged.description, "Review");
+        assert_eq!(unchanged.start_date, "2025-04-07");
+        assert_eq!(unchanged.start_time.as_deref(), Some("09:00:00"));
+        assert_eq!(unchanged.attributes.len(), 1);
+
+        let changes = WorklogChanges {
+            time_spent: Some("1h30m".to_string()),
+            comment: Some("Code review".to_string()),
+            date: Some("2025-04-08".to_string()),
+            start_time: Some("10:15".to_string()),
+        };
+        let changed = apply_changes(&worklog_item(3600), &changes, None, &durations).unwrap();
+        assert_eq!(changed.time_spent_seconds, 5400);
+        assert_eq!(changed.billable_seconds, Some(5400));
+        assert_eq!(changed.description, "Code review");
+        assert_eq!(changed.start_date, "2025-04-08");
+        assert_eq!(changed.start_time.as_deref(), Some("10:15:00"));
+
+        // Billable time that differs from the time spent is kept, unless given
+        let changed = apply_changes(&worklog_item(0), &changes, None, &durations).unwrap();
+        assert_eq!(changed.billable_seconds, Some(0));
+        let changed = apply_changes(&worklog_item(0), &changes, Some(1800), &durations).unwrap();
+        assert_eq!(changed.billable_seconds, Some(1800));
+
+        let invalid = WorklogChanges {
+            start_time: Some("25:00".to_string()),
+            ..WorklogChanges::default()
+        };
+        assert!(apply_changes(&worklog_item(0), &invalid, None, &durations).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_worklog_overlap_past_midnight() {
+        let test_db_path = "test_update_worklog_midnight";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_test_api(test_db_path, &server.url());
+        let worklog = |id: i64, date: &str, start_time: &str, seconds: i32| {
+            format!(
+                r#"{{"tempoWorklogId": {}, "timeSpentSeconds": {}, "description": "Deploy",
+                    "startDate": "{}", "startTime": "{}", "createdAt": "2025-04-07T10:00:00Z",
+                    "issue": {{"id": 10001}}}}"#,
+                id, seconds, date, start_time
+            )
+        };
+
+        server
+            .mock("GET", "/worklogs/5")
+            .with_body(worklog(5, "2025-04-08", "02:00:00", 3600))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/work-attributes")
+            .with_body(r#"{"results": []}"#)
+            .create_async()
+            .await;
+        // Worklog 7 runs from 23:00 the evening before until 01:00
+        server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(Matcher::UrlEncoded(
+                "from".to_string(),
+                "2025-04-07".to_string(),
+            ))
+            .with_body(format!(
+                r#"{{"results": [{}, {}]}}"#,
+                worklog(7, "2025-04-07", "23:00:00", 7200),
+                worklog(5, "2025-04-08", "02:00:00", 3600)
+            ))
+            .create_async()
+            .await;
+
+        let changes = WorklogChanges {
+            start_time: Some("00:30".to_string()),
+            ..WorklogChanges::default()
+        };
+        let error = update_worklog(&api, "5", &changes, &WorklogArgs::default(), false)
+            .await
+            .unwrap_err();
+        assert!(error.contains("overlaps"), "{}", error);
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+}
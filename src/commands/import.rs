@@ -0,0 +1,196 @@
+use crate::api::ApiClient;
+use crate::commands::create::{review_and_create_worklogs, CreateOptions};
+use crate::importers::{ImportMapping, ImportSource, TimeEntry};
+use crate::models::NewWorklog;
+
+use chrono::NaiveDate;
+use std::path::{Path, PathBuf};
+
+#[allow(clippy::too_many_arguments)]
+pub async fn import(
+    api: &ApiClient,
+    source: ImportSource,
+    file: &Path,
+    date_from: Option<String>,
+    date_to: Option<String>,
+    mapping_file: Option<PathBuf>,
+    default_issue: Option<String>,
+    date_format: Option<String>,
+    yes: bool,
+    options: &CreateOptions,
+) {
+    let worklogs = match read_worklogs(
+        source,
+        file,
+        date_from.as_deref(),
+        date_to.as_deref(),
+        mapping_file,
+        default_issue,
+        date_format,
+    ) {
+        Ok(worklogs) => worklogs,
+        Err(e) => {
+            eprintln!("Error. {}", e);
+            return;
+        }
+    };
+
+    if worklogs.is_empty() {
+        println!("No time entries to import");
+        return;
+    }
+
+    review_and_create_worklogs(api, worklogs, yes, options).await;
+}
+
+fn read_worklogs(
+    source: ImportSource,
+    file: &Path,
+    date_from: Option<&str>,
+    date_to: Option<&str>,
+    mapping_file: Option<PathBuf>,
+    default_issue: Option<String>,
+    date_format: Option<String>,
+) -> Result<Vec<NewWorklog>, String> {
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+
+    let mut mapping = ImportMapping::load(mapping_file.as_deref())?;
+    if default_issue.is_some() {
+        mapping.default = default_issue;
+    }
+
+    let date_from = date_from.map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap());
+    let date_to = date_to.map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap());
+
+    let entries: Vec<TimeEntry> = source
+        .reader(date_format)
+        .read(&content)?
+        .into_iter()
+        .filter(|entry| date_from.map_or(true, |date_from| entry.date >= date_from))
+        .filter(|entry| date_to.map_or(true, |date_to| entry.date <= date_to))
+        .collect();
+
+    let (worklogs, skipped) = map_entries_to_worklogs(entries, &mapping);
+
+    if !skipped.is_empty() {
+        println!(
+            "Skipped {} time entry(s) with no mapped issue:",
+            skipped.len()
+        );
+        for entry in skipped {
+            println!(
+                "  {} {} ({})",
+                entry.date,
+                entry.description,
+                if entry.project.is_empty() {
+                    "no project"
+                } else {
+                    &entry.project
+                }
+            );
+        }
+    }
+
+    Ok(worklogs)
+}
+
+// Turn time entries into worklogs. Entries without a mapped issue or duration are skipped
+fn map_entries_to_worklogs(
+    entries: Vec<TimeEntry>,
+    mapping: &ImportMapping,
+) -> (Vec<NewWorklog>, Vec<TimeEntry>) {
+    let mut worklogs: Vec<NewWorklog> = Vec::new();
+    let mut skipped: Vec<TimeEntry> = Vec::new();
+
+    for entry in entries.into_iter().filter(|e| e.duration_seconds > 0) {
+        let Some(issue_key) = mapping.match_issue(&entry) else {
+            skipped.push(entry);
+            continue;
+        };
+
+        worklogs.push(NewWorklog {
+            issue_key,
+            time_spent_seconds: entry.duration_seconds,
+            description: if entry.description.is_empty() {
+                entry.project
+            } else {
+                entry.description
+            },
+            start_date: entry.date.format("%Y-%m-%d").to_string(),
+            start_time: entry
+                .start_time
+                .map(|time| time.format("%H:%M:%S").to_string()),
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        });
+    }
+
+    (worklogs, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_worklogs() {
+        let test_file_path = "test_import_read_worklogs.csv";
+        std::fs::write(
+            test_file_path,
+            "Date,Client,Project,Project Code,Task,Notes,Hours
+2025-04-07,Acme,Website,WEB,Development,Fix header,1.5
+2025-04-08,Acme,Website,WEB,Meeting,,0.25
+2025-04-09,Acme,Unknown,,,Something,2
+2025-04-20,Acme,Website,WEB,Development,Later,1
+",
+        )
+        .unwrap();
+
+        let test_mapping_path = "test_import_read_worklogs.toml";
+        std::fs::write(
+            test_mapping_path,
+            "[projects]\nWebsite = \"WEB-1\"\n\n[tags]\nMeeting = \"standup\"\n",
+        )
+        .unwrap();
+
+        let worklogs = read_worklogs(
+            ImportSource::Harvest,
+            Path::new(test_file_path),
+            Some("2025-04-01"),
+            Some("2025-04-10"),
+            Some(PathBuf::from(test_mapping_path)),
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(worklogs.len(), 2);
+        assert_eq!(worklogs[0].issue_key, "WEB-1");
+        assert_eq!(worklogs[0].time_spent_seconds, 5400);
+        assert_eq!(worklogs[0].description, "Fix header");
+        assert_eq!(worklogs[0].start_date, "2025-04-07");
+        assert_eq!(worklogs[0].start_time, None);
+        assert_eq!(worklogs[1].issue_key, "standup");
+        assert_eq!(worklogs[1].description, "Website");
+
+        // The default issue picks up the entries without mapping
+        let worklogs = read_worklogs(
+            ImportSource::Harvest,
+            Path::new(test_file_path),
+            None,
+            None,
+            Some(PathBuf::from(test_mapping_path)),
+            Some("INTERNAL-1".to_string()),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(worklogs.len(), 4);
+        assert_eq!(worklogs[2].issue_key, "INTERNAL-1");
+
+        let _ = std::fs::remove_file(test_file_path);
+        let _ = std::fs::remove_file(test_mapping_path);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::api::ApiClient;
+use crate::commands::create::{review_and_create_worklogs, CreateOptions};
+use crate::importers::calendar::{parse_events, CalendarEvent, CalendarRules};
+use crate::models::NewWorklog;
+
+use chrono::NaiveDate;
+use std::path::{Path, PathBuf};
+
+// Event that wasn't imported and the reason why
+type SkippedEvent<'a> = (&'a CalendarEvent, &'static str);
+
+#[allow(clippy::too_many_arguments)]
+pub async fn import_calendar(
+    api: &ApiClient,
+    file: &Path,
+    date_from: &str,
+    date_to: &str,
+    rules_file: Option<PathBuf>,
+    default_issue: Option<String>,
+    yes: bool,
+    options: &CreateOptions,
+) {
+    let worklogs = match read_calendar_worklogs(
+        file,
+        date_from,
+        date_to,
+        rules_file,
+        default_issue,
+        &api.config.jira_email,
+    ) {
+        Ok(worklogs) => worklogs,
+        Err(e) => {
+            eprintln!("Error. {}", e);
+            return;
+        }
+    };
+
+    if worklogs.is_empty() {
+        println!("No events to import from {} to {}", date_from, date_to);
+        return;
+    }
+
+    review_and_create_worklogs(api, worklogs, yes, options).await;
+}
+
+fn read_calendar_worklogs(
+    file: &Path,
+    date_from: &str,
+    date_to: &str,
+    rules_file: Option<PathBuf>,
+    default_issue: Option<String>,
+    email: &str,
+) -> Result<Vec<NewWorklog>, String> {
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+
+    let mut rules = CalendarRules::load(rules_file.as_deref())?;
+    if default_issue.is_some() {
+        rules.default = default_issue;
+    }
+
+    let date_from = NaiveDate::parse_from_str(date_from, "%Y-%m-%d").unwrap();
+    let date_to = NaiveDate::parse_from_str(date_to, "%Y-%m-%d").unwrap();
+
+    let events: Vec<CalendarEvent> = parse_events(&content, email, date_from, date_to);
+
+    let (worklogs, skipped) = map_events_to_worklogs(&events, &rules)?;
+
+    if !skipped.is_empty() {
+        println!("Skipped {} event(s):", skipped.len());
+        for (event, reason) in skipped {
+            println!(
+                "  {} {} ({})",
+                event.start.format("%Y-%m-%d %H:%M"),
+                event.summary,
+                reason
+            );
+        }
+    }
+
+    Ok(worklogs)
+}
+
+// Turn events into worklogs. Declined, cancelled, all-day and unsupported events are skipped,
+// as well as the ones no rule matches
+fn map_events_to_worklogs<'a>(
+    events: &'a [CalendarEvent],
+    rules: &CalendarRules,
+) -> Result<(Vec<NewWorklog>, Vec<SkippedEvent<'a>>), String> {
+    let mut worklogs: Vec<NewWorklog> = Vec::new();
+    let mut skipped: Vec<SkippedEvent> = Vec::new();
+
+    for event in events {
+        let time_spent_seconds = (event.end - event.start).num_seconds() as i32;
+
+        let reason = if let Some(reason) = event.unsupported {
+            reason
+        } else if event.declined {
+            "declined"
+        } else if event.cancelled {
+            "cancelled"
+        } else if event.all_day {
+            "all-day"
+        } else if time_spent_seconds <= 0 {
+            "no duration"
+        } else {
+            match rules.match_issue(event)? {
+                Some(issue_key) => {
+                    worklogs.push(NewWorklog {
+                        issue_key,
+                        time_spent_seconds,
+                        description: event.summary.clone(),
+                        start_date: event.start.format("%Y-%m-%d").to_string(),
+                        start_time: Some(event.start.format("%H:%M:%S").to_string()),
+                        billable_seconds: None,
+                        attributes: Vec::new(),
+                        remaining_estimate_seconds: None,
+                    });
+                    continue;
+                }
+                None => "no matching rule",
+            }
+        };
+
+        skipped.push((event, reason));
+    }
+
+    Ok((worklogs, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::calendar::CalendarRule;
+    use chrono::NaiveDateTime;
+
+    fn event(summary: &str, start: &str, end: &str) -> CalendarEvent {
+        CalendarEvent {
+            summary: summary.to_string(),
+            organizer: None,
+            start: NaiveDateTime::parse_from_str(start, "%Y-%m-%d %H:%M").unwrap(),
+            end: NaiveDateTime::parse_from_str(end, "%Y-%m-%d %H:%M").unwrap(),
+            all_day: false,
+            declined: false,
+            cancelled: false,
+            unsupported: None,
+        }
+    }
+
+    #[test]
+    fn test_map_events_to_worklogs() {
+        let rules = CalendarRules {
+            default: None,
+            rules: vec![CalendarRule {
+                summary: Some("(?i)standup".to_string()),
+                organizer: None,
+                issue: "standup".to_string(),
+            }],
+        };
+
+        let events = vec![
+            event("Standup", "2025-04-07 10:00", "2025-04-07 10:15"),
+            CalendarEvent {
+                declined: true,
+                ..event("Standup", "2025-04-08 10:00", "2025-04-08 10:15")
+            },
+            CalendarEvent {
+                all_day: true,
+                ..event("Standup", "2025-04-09 00:00", "2025-04-10 00:00")
+            },
+            event("Lunch", "2025-04-09 12:00", "2025-04-09 13:00"),
+            CalendarEvent {
+                unsupported: Some("in another timezone"),
+                ..event("Standup", "2025-04-10 10:00", "2025-04-10 10:15")
+            },
+        ];
+
+        let (worklogs, skipped) = map_events_to_worklogs(&events, &rules).unwrap();
+
+        assert_eq!(worklogs.len(), 1);
+        assert_eq!(worklogs[0].issue_key, "standup");
+        assert_eq!(worklogs[0].time_spent_seconds, 900);
+        assert_eq!(worklogs[0].description, "Standup");
+        assert_eq!(worklogs[0].start_date, "2025-04-07");
+        assert_eq!(worklogs[0].start_time, Some("10:00:00".to_string()));
+
+        let reasons: Vec<&str> = skipped.iter().map(|(_, reason)| *reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "declined",
+                "all-day",
+                "no matching rule",
+                "in another timezone"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_calendar_worklogs() {
+        let test_file_path = "test_read_calendar_worklogs.ics";
+        std::fs::write(
+            test_file_path,
+            "BEGIN:VCALENDAR
+BEGIN:VEVENT
+SUMMARY:Planning
+DTSTART:20250407T090000
+DTEND:20250407T100000
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:Retro
+DTSTART:20250420T090000
+DTEND:20250420T100000
+END:VEVENT
+END:VCALENDAR
+",
+        )
+        .unwrap();
+
+        let worklogs = read_calendar_worklogs(
+            Path::new(test_file_path),
+            "2025-04-01",
+            "2025-04-10",
+            Some(PathBuf::from("missing-rules.toml")),
+            None,
+            "me@example.com",
+        );
+        assert!(worklogs.is_err());
+
+        let worklogs = read_calendar_worklogs(
+            Path::new(test_file_path),
+            "2025-04-01",
+            "2025-04-10",
+            None,
+            Some("INTERNAL-1".to_string()),
+            "me@example.com",
+        )
+        .unwrap();
+
+        assert_eq!(worklogs.len(), 1);
+        assert_eq!(worklogs[0].issue_key, "INTERNAL-1");
+        assert_eq!(worklogs[0].description, "Planning");
+        assert_eq!(worklogs[0].time_spent_seconds, 3600);
+
+        let _ = std::fs::remove_file(test_file_path);
+    }
+}
@@ -0,0 +1,368 @@
+use crate::api::{ApiClient, ApiTrait, CONCURRENT_REQUESTS};
+use crate::commands::approvals::user_name;
+use crate::commands::list::{apply_common_formatting, truncate_string};
+use crate::models::{TimeTracking, WorklogItem};
+use crate::utils::format_duration;
+
+use futures::{stream, StreamExt};
+use spinners::{Spinner, Spinners};
+use std::collections::{HashMap, HashSet};
+use tabled::{
+    builder::Builder,
+    settings::object::Rows,
+    settings::{Alignment, Span},
+    Table,
+};
+
+pub async fn issue_log(
+    api: &ApiClient,
+    issue_key: &str,
+    date_from: Option<String>,
+    date_to: Option<String>,
+) {
+    let mut spinner = Spinner::new(Spinners::Dots, "Retrieving worklogs...".to_string());
+    let issue_key = api.storage.expand_alias(issue_key);
+
+    let result = async {
+        let issue = api.get_jira_issue(&issue_key).await?;
+        let worklogs = api
+            .list_issue_worklogs(&issue.id, &date_query(&date_from, &date_to))
+            .await?;
+        let estimate = api.get_time_tracking(&issue.key).await?;
+
+        Ok::<_, String>((issue.key, worklogs, estimate))
+    }
+    .await;
+
+    match result {
+        Ok((key, worklogs, estimate)) => {
+            let output = render_log(api, &key, &worklogs, &estimate).await;
+            spinner.stop_with_message(format!("\n{}", output));
+        }
+        Err(e) => spinner.stop_with_message(format!("\nError. {}", e)),
+    }
+}
+
+pub async fn project_log(api: &ApiClient, project_key: &str, date_from: &str, date_to: &str) {
+    let mut spinner = Spinner::new(Spinners::Dots, "Retrieving worklogs...".to_string());
+
+    let result = async {
+        let project = api.get_jira_project(project_key).await?;
+        let worklogs = api
+            .list_project_worklogs(&project.id, &[("from", date_from), ("to", date_to)])
+            .await?;
+        let (estimate, unread) = project_estimate(api, &worklogs).await;
+
+        Ok::<_, String>((project.key, worklogs, estimate, unread))
+    }
+    .await;
+
+    match result {
+        Ok((key, worklogs, estimate, unread)) => {
+            let title = format!("{} from {} to {}", key, date_from, date_to);
+            let mut output = render_log(api, &title, &worklogs, &estimate).await;
+            if !unread.is_empty() {
+                output.push_str(&format!(
+                    "\nCouldn't read the estimates of {}",
+                    unread.join(", ")
+                ));
+            }
+            spinner.stop_with_message(format!("\n{}", output));
+        }
+        Err(e) => spinner.stop_with_message(format!("\nError. {}", e)),
+    }
+}
+
+fn date_query<'a>(
+    date_from: &'a Option<String>,
+    date_to: &'a Option<String>,
+) -> Vec<(&'a str, &'a str)> {
+    let mut query = Vec::new();
+
+    if let Some(date_from) = date_from {
+        query.push(("from", date_from.as_str()));
+    }
+    if let Some(date_to) = date_to {
+        query.push(("to", date_to.as_str()));
+    }
+
+    query
+}
+
+// Add up the estimates of the issues time was logged on. Issues whose estimates can't be
+// read are left out and returned
+async fn project_estimate(
+    api: &ApiClient,
+    worklogs: &[WorklogItem],
+) -> (TimeTracking, Vec<String>) {
+    let issue_keys: HashSet<String> = worklogs
+        .iter()
+        .filter_map(|worklog| worklog.jira_issue.as_ref().map(|issue| issue.key.clone()))
+        .collect();
+
+    let results: Vec<(String, Result<TimeTracking, String>)> = stream::iter(issue_keys)
+        .map(|issue_key| async move {
+            let estimate = api.get_time_tracking(&issue_key).await;
+            (issue_key, estimate)
+        })
+        .buffer_unordered(CONCURRENT_REQUESTS)
+        .collect()
+        .await;
+
+    let mut estimates = Vec::new();
+    let mut unread = Vec::new();
+    for (issue_key, estimate) in results {
+        match estimate {
+            Ok(estimate) => estimates.push(estimate),
+            Err(_) => unread.push(issue_key),
+        }
+    }
+    unread.sort();
+
+    (sum_estimates(&estimates), unread)
+}
+
+fn sum_estimates(estimates: &[TimeTracking]) -> TimeTracking {
+    let sum = |field: fn(&TimeTracking) -> Option<i32>| {
+        estimates
+            .iter()
+            .filter_map(field)
+            .reduce(|total, seconds| total + seconds)
+    };
+
+    TimeTracking {
+        original_estimate_seconds: sum(|e| e.original_estimate_seconds),
+        remaining_estimate_seconds: sum(|e| e.remaining_estimate_seconds),
+        time_spent_seconds: sum(|e| e.time_spent_seconds),
+    }
+}
+
+async fn render_log(
+    api: &ApiClient,
+    title: &str,
+    worklogs: &[WorklogItem],
+    estimate: &TimeTracking,
+) -> String {
+    let authors: HashSet<&str> = worklogs.iter().map(author_id).collect();
+    let names: HashMap<String, String> = stream::iter(authors)
+        .map(|account_id| async move { (account_id.to_string(), user_name(api, account_id).await) })
+        .buffer_unordered(CONCURRENT_REQUESTS)
+        .collect()
+        .await;
+
+    format!(
+        "{}\n{}\n{}",
+        build_worklogs_table(title, worklogs, &names),
+        build_authors_table(worklogs, &names),
+        format_estimate(
+            worklogs.iter().map(|w| w.time_spent_seconds).sum(),
+            estimate
+        )
+    )
+}
+
+fn build_worklogs_table(
+    title: &str,
+    worklogs: &[WorklogItem],
+    names: &HashMap<String, String>,
+) -> Table {
+    let mut builder = Builder::default();
+
+    builder.push_record(vec![title]);
+    builder.push_record(vec!["Author", "Date", "Duration", "Issue", "Description"]);
+
+    let mut sorted: Vec<&WorklogItem> = worklogs.iter().collect();
+    sorted.sort_by(|a, b| (&a.start_date, &a.start_time).cmp(&(&b.start_date, &b.start_time)));
+
+    for worklog in sorted {
+        builder.push_record(vec![
+            author_name(worklog, names),
+            worklog.start_date.clone(),
+            format_duration(worklog.time_spent_seconds),
+            worklog
+                .jira_issue
+                .as_ref()
+                .map(|issue| issue.key.clone())
+                .unwrap_or_else(|| worklog.issue.id.to_string()),
+            truncate_string(&worklog.description, 100),
+        ]);
+    }
+
+    builder.push_record(vec![format!(
+        "{} worklogs, {}",
+        worklogs.len(),
+        format_duration(worklogs.iter().map(|w| w.time_spent_seconds).sum())
+    )]);
+
+    let mut table = builder.build();
+    table.modify(Rows::first(), Span::column(5));
+    table.modify(Rows::last(), Span::column(5));
+    table.modify(Rows::first(), Alignment::center());
+    table.modify(Rows::last(), Alignment::right());
+    apply_common_formatting(&mut table);
+
+    table
+}
+
+fn build_authors_table(worklogs: &[WorklogItem], names: &HashMap<String, String>) -> Table {
+    let mut totals: HashMap<String, i32> = HashMap::new();
+    for worklog in worklogs {
+        *totals.entry(author_name(worklog, names)).or_default() += worklog.time_spent_seconds;
+    }
+
+    let mut totals: Vec<(String, i32)> = totals.into_iter().collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut builder = Builder::default();
+    builder.push_record(vec!["Author", "Duration"]);
+    for (author, seconds) in totals {
+        builder.push_record(vec![author, format_duration(seconds)]);
+    }
+
+    let mut table = builder.build();
+    table.with(tabled::settings::Style::modern());
+
+    table
+}
+
+// e.g "Logged 12h, original estimate 10h, remaining 0h, 2h over the estimate". `logged` may
+// only cover a date range, so the overrun is based on the time Jira has on the whole issue
+pub fn format_estimate(logged: i32, estimate: &TimeTracking) -> String {
+    let mut parts = vec![format!("Logged {}", format_duration(logged))];
+    let spent = estimate.time_spent_seconds.unwrap_or(0);
+
+    if spent != logged && spent > 0 {
+        parts.push(format!("{} in total", format_duration(spent)));
+    }
+
+    match estimate.original_estimate_seconds {
+        Some(original) => parts.push(format!("original estimate {}", format_duration(original))),
+        None => parts.push("no original estimate".to_string()),
+    }
+
+    if let Some(remaining) = estimate.remaining_estimate_seconds {
+        parts.push(format!("remaining {}", format_duration(remaining)));
+    }
+
+    if let Some(original) = estimate.original_estimate_seconds {
+        if spent > original {
+            parts.push(format!(
+                "{} over the estimate",
+                format_duration(spent - original)
+            ));
+        }
+    }
+
+    parts.join(", ")
+}
+
+fn author_id(worklog: &WorklogItem) -> &str {
+    worklog
+        .author
+        .as_ref()
+        .map(|author| author.account_id.as_str())
+        .unwrap_or("Unknown")
+}
+
+fn author_name(worklog: &WorklogItem, names: &HashMap<String, String>) -> String {
+    let account_id = author_id(worklog);
+
+    names
+        .get(account_id)
+        .cloned()
+        .unwrap_or_else(|| account_id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TempoIssue, TempoUser, WorklogAttributes};
+
+    fn worklog_item(author: &str, date: &str, seconds: i32) -> WorklogItem {
+        WorklogItem {
+            tempo_worklog_id: 1,
+            time_spent_seconds: seconds,
+            billable_seconds: seconds,
+            description: "Work".to_string(),
+            start_date: date.to_string(),
+            start_time: "09:00:00".to_string(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            issue: TempoIssue { id: 123 },
+            author: Some(TempoUser {
+                account_id: author.to_string(),
+            }),
+            attributes: WorklogAttributes::default(),
+            jira_issue: None,
+        }
+    }
+
+    #[test]
+    fn test_build_log_tables() {
+        let worklogs = vec![
+            worklog_item("jane", "2025-04-08", 3600),
+            worklog_item("john", "2025-04-07", 1800),
+            worklog_item("jane", "2025-04-09", 7200),
+        ];
+        let names = HashMap::from([("jane".to_string(), "Jane Doe".to_string())]);
+
+        let worklogs_table = build_worklogs_table("ABC-1", &worklogs, &names).to_string();
+        assert!(worklogs_table.contains("Jane Doe"));
+        assert!(worklogs_table.contains("3 worklogs, 3h30m"));
+        assert!(worklogs_table.find("2025-04-07") < worklogs_table.find("2025-04-08"));
+
+        let authors_table = build_authors_table(&worklogs, &names).to_string();
+        assert!(authors_table.contains("3h"));
+        assert!(authors_table.find("Jane Doe") < authors_table.find("john"));
+    }
+
+    #[test]
+    fn test_format_estimate() {
+        let estimate = TimeTracking {
+            original_estimate_seconds: Some(10 * 3600),
+            remaining_estimate_seconds: Some(0),
+            time_spent_seconds: Some(12 * 3600),
+        };
+
+        assert_eq!(
+            format_estimate(12 * 3600, &estimate),
+            "Logged 12h, original estimate 10h, remaining 0h, 2h over the estimate"
+        );
+        // Only a date range is logged, the issue isn't over its estimate
+        let estimate = TimeTracking {
+            original_estimate_seconds: Some(10 * 3600),
+            remaining_estimate_seconds: Some(4 * 3600),
+            time_spent_seconds: Some(6 * 3600),
+        };
+        assert_eq!(
+            format_estimate(4 * 3600, &estimate),
+            "Logged 4h, 6h in total, original estimate 10h, remaining 4h"
+        );
+
+        assert_eq!(
+            format_estimate(3600, &TimeTracking::default()),
+            "Logged 1h, no original estimate"
+        );
+    }
+
+    #[test]
+    fn test_sum_estimates() {
+        let estimates = vec![
+            TimeTracking {
+                original_estimate_seconds: Some(3600),
+                remaining_estimate_seconds: None,
+                time_spent_seconds: Some(1800),
+            },
+            TimeTracking {
+                original_estimate_seconds: Some(7200),
+                remaining_estimate_seconds: None,
+                time_spent_seconds: None,
+            },
+        ];
+
+        let total = sum_estimates(&estimates);
+        assert_eq!(total.original_estimate_seconds, Some(10800));
+        assert_eq!(total.remaining_estimate_seconds, None);
+        assert_eq!(total.time_spent_seconds, Some(1800));
+    }
+}
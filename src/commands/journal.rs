@@ -0,0 +1,285 @@
+use crate::api::{ApiClient, ApiTrait};
+use crate::commands::approvals::approved_periods;
+use crate::commands::list::truncate_string;
+use crate::models::{JournalEntry, JournalOperation, NewWorklog, WorklogItem};
+use crate::storage::Storage;
+use crate::utils::format_duration;
+use crate::validation::check_approved_periods;
+
+use chrono::Local;
+use spinners::{Spinner, Spinners};
+use tabled::{builder::Builder, settings::Style};
+
+pub fn record_created(storage: &Storage, created: &[(NewWorklog, WorklogItem)]) {
+    for (worklog, item) in created {
+        record(
+            storage,
+            JournalOperation::Create,
+            &item.tempo_worklog_id.to_string(),
+            None,
+            Some(worklog.clone()),
+        );
+    }
+}
+
+pub fn record_edited(storage: &Storage, worklog_id: &str, before: NewWorklog, after: NewWorklog) {
+    record(
+        storage,
+        JournalOperation::Edit,
+        worklog_id,
+        Some(before),
+        Some(after),
+    );
+}
+
+pub fn record_deleted(storage: &Storage, worklog_id: &str, before: NewWorklog) {
+    record(
+        storage,
+        JournalOperation::Delete,
+        worklog_id,
+        Some(before),
+        None,
+    );
+}
+
+fn record(
+    storage: &Storage,
+    operation: JournalOperation,
+    worklog_id: &str,
+    before: Option<NewWorklog>,
+    after: Option<NewWorklog>,
+) {
+    storage.add_journal_entry(JournalEntry {
+        id: 0,
+        performed_at: Local::now().to_rfc3339(),
+        operation,
+        worklog_id: worklog_id.to_string(),
+        before,
+        after,
+        undone: false,
+    });
+}
+
+pub fn history(storage: &Storage, limit: usize) {
+    let entries = storage.list_journal_entries(limit);
+
+    if entries.is_empty() {
+        println!("No history yet");
+        return;
+    }
+
+    println!("{}", build_history_table(&entries));
+}
+
+// Revert the last `count` operations that weren't undone yet, latest first
+pub async fn undo(api: &ApiClient, count: usize, force: bool) {
+    let entries: Vec<JournalEntry> = api
+        .storage
+        .list_journal_entries(usize::MAX)
+        .into_iter()
+        .filter(|entry| !entry.undone)
+        .take(count)
+        .collect();
+
+    if entries.is_empty() {
+        println!("Nothing to undo");
+        return;
+    }
+
+    let mut spinner = Spinner::new(Spinners::Dots, "Undoing...".to_string());
+    let mut undone = Vec::new();
+
+    for mut entry in entries {
+        match undo_entry(api, &entry, force).await {
+            Ok(message) => {
+                entry.undone = true;
+                api.storage.update_journal_entry(&entry);
+                undone.push(message);
+            }
+            Err(e) => {
+                undone.push(format!("Error. Failed to undo {}: {}", describe(&entry), e));
+                break;
+            }
+        }
+    }
+
+    spinner.stop_with_message(format!("\n{}", undone.join("\n")));
+}
+
+async fn undo_entry(api: &ApiClient, entry: &JournalEntry, force: bool) -> Result<String, String> {
+    if !force {
+        check_undo_approvals(api, entry).await?;
+    }
+
+    match (entry.operation, &entry.before) {
+        (JournalOperation::Create, _) => {
+            api.delete_worklogs(&vec![entry.worklog_id.clone()]).await?;
+            Ok(format!("Deleted worklog {}", entry.worklog_id))
+        }
+        (JournalOperation::Edit, Some(before)) => {
+            api.update_worklog(&entry.worklog_id, before).await?;
+            Ok(format!("Restored worklog {}", entry.worklog_id))
+        }
+        (JournalOperation::Delete, Some(before)) => {
+            let item = api.log_time(before).await?;
+            let new_id = item.tempo_worklog_id.to_string();
+            api.storage
+                .replace_journal_worklog_id(&entry.worklog_id, &new_id);
+            Ok(format!(
+                "Recreated worklog {} as {}",
+                entry.worklog_id, new_id
+            ))
+        }
+        _ => Err("the worklog before the change wasn't recorded".to_string()),
+    }
+}
+
+// Undoing can't change worklogs in approved periods, neither on the dates before the
+// operation nor after it
+async fn check_undo_approvals(api: &ApiClient, entry: &JournalEntry) -> Result<(), String> {
+    let affected: Vec<NewWorklog> = entry
+        .before
+        .iter()
+        .chain(entry.after.iter())
+        .cloned()
+        .collect();
+    let dates = affected.iter().map(|worklog| worklog.start_date.as_str());
+    let (Some(date_from), Some(date_to)) = (dates.clone().min(), dates.max()) else {
+        return Ok(());
+    };
+
+    let violations = check_approved_periods(
+        &affected,
+        &[],
+        &approved_periods(api, date_from, date_to).await,
+    );
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "use --force to undo anyway:\n  {}",
+            violations.join("\n  ")
+        ))
+    }
+}
+
+fn build_history_table(entries: &[JournalEntry]) -> tabled::Table {
+    let mut builder = Builder::default();
+    builder.push_record(vec![
+        "#",
+        "Time",
+        "Operation",
+        "Worklog",
+        "Issue",
+        "Date",
+        "Duration",
+        "Description",
+        "Undone",
+    ]);
+
+    for entry in entries {
+        let worklog = entry.after.as_ref().or(entry.before.as_ref());
+        let duration = match (&entry.before, &entry.after) {
+            (Some(before), Some(after))
+                if before.time_spent_seconds != after.time_spent_seconds =>
+            {
+                format!(
+                    "{} -> {}",
+                    format_duration(before.time_spent_seconds),
+                    format_duration(after.time_spent_seconds)
+                )
+            }
+            _ => worklog
+                .map(|w| format_duration(w.time_spent_seconds))
+                .unwrap_or_default(),
+        };
+
+        builder.push_record(vec![
+            entry.id.to_string(),
+            chrono::DateTime::parse_from_rfc3339(&entry.performed_at)
+                .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|_| entry.performed_at.clone()),
+            operation_name(entry.operation).to_string(),
+            entry.worklog_id.clone(),
+            worklog.map(|w| w.issue_key.clone()).unwrap_or_default(),
+            worklog.map(|w| w.start_date.clone()).unwrap_or_default(),
+            duration,
+            worklog
+                .map(|w| truncate_string(&w.description, 50))
+                .unwrap_or_default(),
+            if entry.undone { "yes" } else { "" }.to_string(),
+        ]);
+    }
+
+    let mut table = builder.build();
+    table.with(Style::modern());
+
+    table
+}
+
+fn describe(entry: &JournalEntry) -> String {
+    format!(
+        "{} of worklog {}",
+        operation_name(entry.operation),
+        entry.worklog_id
+    )
+}
+
+fn operation_name(operation: JournalOperation) -> &'static str {
+    match operation {
+        JournalOperation::Create => "create",
+        JournalOperation::Edit => "edit",
+        JournalOperation::Delete => "delete",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn worklog(seconds: i32) -> NewWorklog {
+        NewWorklog {
+            issue_key: "ABC-1".to_string(),
+            time_spent_seconds: seconds,
+            description: "Review".to_string(),
+            start_date: "2025-04-07".to_string(),
+            start_time: None,
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        }
+    }
+
+    #[test]
+    fn test_build_history_table() {
+        let entries = vec![
+            JournalEntry {
+                id: 2,
+                performed_at: "2025-04-07T11:00:00+00:00".to_string(),
+                operation: JournalOperation::Edit,
+                worklog_id: "99".to_string(),
+                before: Some(worklog(3600)),
+                after: Some(worklog(5400)),
+                undone: false,
+            },
+            JournalEntry {
+                id: 1,
+                performed_at: "2025-04-07T10:00:00+00:00".to_string(),
+                operation: JournalOperation::Create,
+                worklog_id: "99".to_string(),
+                before: None,
+                after: Some(worklog(3600)),
+                undone: true,
+            },
+        ];
+
+        let table = build_history_table(&entries).to_string();
+
+        assert!(table.contains("2025-04-07 11:00:00"));
+        assert!(table.contains("edit"));
+        assert!(table.contains("1h -> 1h30m"));
+        assert!(table.contains("create"));
+        assert!(table.contains("ABC-1"));
+        assert!(table.contains("yes"));
+    }
+}
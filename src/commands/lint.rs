@@ -0,0 +1,41 @@
+use crate::api::{ApiClient, ApiTrait};
+use crate::validation::lint_worklogs;
+
+use chrono::Local;
+use spinners::{Spinner, Spinners};
+
+pub async fn lint(api: &ApiClient, date_from: &str, date_to: &str) {
+    let mut spinner = Spinner::new(Spinners::Dots, "Retrieving worklogs...".to_string());
+
+    let worklogs = match api.list_worklogs(date_from, date_to).await {
+        Ok(worklogs) => worklogs,
+        Err(e) => {
+            spinner.stop_with_message(format!("\nError. Failed to list worklogs: {}", e));
+            return;
+        }
+    };
+
+    let today = Local::now().date_naive();
+    let violations = lint_worklogs(&worklogs, &api.settings.validation, today);
+
+    if violations.is_empty() {
+        spinner.stop_with_message(format!(
+            "\nNo problems found in {} worklog(s) from {} to {}",
+            worklogs.len(),
+            date_from,
+            date_to
+        ));
+        return;
+    }
+
+    spinner.stop_with_message(format!(
+        "\n{} problem(s) found from {} to {}:",
+        violations.len(),
+        date_from,
+        date_to
+    ));
+
+    for violation in violations {
+        println!("  {}", violation);
+    }
+}
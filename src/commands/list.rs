@@ -1,9 +1,13 @@
 use crate::api::{ApiClient, ApiTrait};
-use crate::models::{UserCredentials, WorklogItem};
+use crate::commands::approvals::approval_summary;
+use crate::commands::mirror::{mirrored_worklogs, MirrorArgs};
+use crate::commands::sync::{pending_labels, pending_worklogs};
+use crate::models::{NewWorklog, UserCredentials, WorkAttributeValue, WorklogItem};
 use crate::storage::Storage;
 use crate::utils;
 
 use spinners::{Spinner, Spinners};
+use std::collections::HashMap;
 use tabled::{
     builder::Builder,
     settings::object::Rows,
@@ -12,16 +16,37 @@ use tabled::{
     Table,
 };
 
-pub async fn list(api: &ApiClient, date: &str) {
+pub async fn list(api: &ApiClient, date: &str, user: Option<&str>, mirror_args: &MirrorArgs) {
     let mut spinner = Spinner::new(Spinners::Dots, "Retrieving worklogs...".to_string());
     let first_day = utils::get_first_day_of_month(date);
     let last_day = utils::get_last_day_of_month(date);
 
-    match api.list_worklogs(&first_day, &last_day).await {
+    let account_id = match resolve_account_id(api, user).await {
+        Ok(account_id) => account_id,
+        Err(e) => {
+            spinner.stop_with_message(format!("\nError. {}", e));
+            return;
+        }
+    };
+
+    let worklogs = match user {
+        None => mirrored_worklogs(api, &first_day, &last_day, mirror_args).await,
+        Some(_) => {
+            api.list_user_worklogs(&account_id, &first_day, &last_day)
+                .await
+        }
+    };
+
+    match worklogs {
         Ok(worklogs) => {
+            let approval = if mirror_args.offline {
+                None
+            } else {
+                approval_summary(api, &account_id, date).await
+            };
             spinner.stop_with_message(format!(
                 "\n{}",
-                build_list_table(worklogs, &date, &api.storage)
+                build_list_table(worklogs, &date, approval, &api.storage, user.is_none())
             ));
         }
         Err(e) => {
@@ -30,27 +55,74 @@ pub async fn list(api: &ApiClient, date: &str) {
     }
 }
 
-fn build_list_table(worklogs: Vec<WorklogItem>, date: &str, storage: &Storage) -> Table {
+// Labels of the queued edits and deletions, and the queued worklogs within the dates. They're
+// the current user's changes, so other users' listings don't get them
+pub fn queued_changes(
+    storage: &Storage,
+    date_from: &str,
+    date_to: &str,
+    own: bool,
+) -> (HashMap<String, &'static str>, Vec<NewWorklog>) {
+    if own {
+        (
+            pending_labels(storage),
+            pending_worklogs(storage, date_from, date_to),
+        )
+    } else {
+        (HashMap::new(), Vec::new())
+    }
+}
+
+// Account ID of the user to list worklogs of, the current user by default
+pub async fn resolve_account_id(api: &ApiClient, user: Option<&str>) -> Result<String, String> {
+    match user {
+        Some(user) => api.resolve_user(user).await,
+        None => Ok(api.config.account_id.clone()),
+    }
+}
+
+fn build_list_table(
+    worklogs: Vec<WorklogItem>,
+    date: &str,
+    approval: Option<String>,
+    storage: &Storage,
+    own: bool,
+) -> Table {
     let config = storage.get_credentials().unwrap();
     let mut builder = Builder::default();
     let mut total_time = 0;
+    let mut total_billable = 0;
 
     add_header_rows(
         &mut builder,
         calculate_total_time(&worklogs),
         utils::working_seconds_in_month(date),
         date,
+        approval,
     );
 
     add_column_headers(&mut builder);
 
+    let attribute_names = work_attribute_names(storage);
+    let day_worklogs = filter_out_worklogs_by_date(&worklogs, date);
+    let (labels, pending) = queued_changes(storage, date, date, own);
     add_list_worklog_rows(
         &mut builder,
-        &filter_out_worklogs_by_date(&worklogs, date),
+        &day_worklogs,
         &config,
+        storage,
+        &attribute_names,
+        &labels,
         &mut total_time,
+        &mut total_billable,
+    );
+    add_pending_worklog_rows(&mut builder, &pending, &config, &attribute_names);
+    add_list_footer_row(
+        &mut builder,
+        total_time,
+        total_billable,
+        inaccessible_issues_warning(&day_worklogs, storage),
     );
-    add_list_footer_row(&mut builder, total_time);
 
     let mut table = builder.build();
     apply_list_table_formatting(&mut table);
@@ -58,7 +130,13 @@ fn build_list_table(worklogs: Vec<WorklogItem>, date: &str, storage: &Storage) -
     table
 }
 
-fn add_header_rows(builder: &mut Builder, worked_seconds: i32, working_seconds: i32, date: &str) {
+fn add_header_rows(
+    builder: &mut Builder,
+    worked_seconds: i32,
+    working_seconds: i32,
+    date: &str,
+    approval: Option<String>,
+) {
     builder.push_record(vec![format!(
         "{} {}/{} (-{})",
         utils::get_month_name(date),
@@ -68,69 +146,176 @@ fn add_header_rows(builder: &mut Builder, worked_seconds: i32, working_seconds:
     )
     .as_str()]);
 
-    builder.push_record(vec![format!(
-        "{} ({})",
-        utils::get_day_name_from_iso8601(date),
-        date
-    )
-    .as_str()]);
+    let day = format!("{} ({})", utils::get_day_name_from_iso8601(date), date);
+    builder.push_record(vec![match approval {
+        Some(approval) => format!("{}, {}", day, approval),
+        None => day,
+    }]);
 }
 
 pub fn add_column_headers(builder: &mut Builder) {
     builder.push_record(vec![
         "ID",
         "Duration",
+        "Billable",
         "Created At",
         "Description",
+        "Attributes",
         "Issue URL",
     ]);
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn add_list_worklog_rows(
     builder: &mut Builder,
     worklogs: &Vec<&WorklogItem>,
     config: &UserCredentials,
+    storage: &Storage,
+    attribute_names: &HashMap<String, String>,
+    pending: &HashMap<String, &'static str>,
     total_time: &mut i32,
+    total_billable: &mut i32,
 ) {
     for worklog in worklogs {
         *total_time += worklog.time_spent_seconds;
+        *total_billable += worklog.billable_seconds;
 
+        let id = worklog.tempo_worklog_id.to_string();
         builder.push_record(vec![
-            worklog.tempo_worklog_id.to_string(),
+            match pending.get(&id) {
+                Some(label) => format!("{} ({})", id, label),
+                None => id,
+            },
             utils::format_duration(worklog.time_spent_seconds),
+            utils::format_duration(worklog.billable_seconds),
             chrono::DateTime::parse_from_rfc3339(&worklog.created_at)
                 .unwrap()
                 .with_timezone(&chrono::Local)
                 .format("%Y-%m-%d %H:%M:%S")
                 .to_string(),
             truncate_string(&worklog.description, 100),
-            format!(
-                "{}/browse/{}",
-                config.url,
-                worklog.jira_issue.as_ref().unwrap().key
+            format_work_attributes(&worklog.attributes.values, attribute_names),
+            match &worklog.jira_issue {
+                Some(issue) => format!("{}/browse/{}", config.url, issue.key),
+                None if is_inaccessible(worklog, storage) => {
+                    format!("{} (inaccessible)", worklog.issue.id)
+                }
+                // Not cached while offline
+                None => worklog.issue.id.to_string(),
+            },
+        ]);
+    }
+}
+
+fn is_inaccessible(worklog: &WorklogItem, storage: &Storage) -> bool {
+    worklog.jira_issue.is_none()
+        && storage
+            .get_inaccessible_issue(&worklog.issue.id.to_string())
+            .is_some()
+}
+
+// Warn about worklogs on issues that are deleted or not visible to the user
+pub fn inaccessible_issues_warning(worklogs: &[&WorklogItem], storage: &Storage) -> Option<String> {
+    let mut issue_ids: Vec<String> = worklogs
+        .iter()
+        .filter(|worklog| is_inaccessible(worklog, storage))
+        .map(|worklog| worklog.issue.id.to_string())
+        .collect();
+    issue_ids.sort();
+    issue_ids.dedup();
+
+    (!issue_ids.is_empty()).then(|| {
+        format!(
+            "Warning. Issue(s) deleted or not visible to you: {}",
+            issue_ids.join(", ")
+        )
+    })
+}
+
+// Worklogs queued while offline. They're not in Tempo yet, so they don't count to the totals
+pub fn add_pending_worklog_rows(
+    builder: &mut Builder,
+    worklogs: &[NewWorklog],
+    config: &UserCredentials,
+    attribute_names: &HashMap<String, String>,
+) {
+    for worklog in worklogs {
+        builder.push_record(vec![
+            "pending".to_string(),
+            utils::format_duration(worklog.time_spent_seconds),
+            utils::format_duration(
+                worklog
+                    .billable_seconds
+                    .unwrap_or(worklog.time_spent_seconds),
             ),
+            String::new(),
+            truncate_string(&worklog.description, 100),
+            format_work_attributes(&worklog.attributes, attribute_names),
+            format!("{}/browse/{}", config.url, worklog.issue_key),
         ]);
     }
 }
 
-fn truncate_string(string: &str, max_length: usize) -> String {
-    if string.len() > max_length {
-        format!("{}...", &string[..max_length])
+// Map work attribute keys to their names, as far as they're cached
+pub fn work_attribute_names(storage: &Storage) -> HashMap<String, String> {
+    storage
+        .get_work_attributes()
+        .into_iter()
+        .map(|attribute| (attribute.key, attribute.name))
+        .collect()
+}
+
+// Format work attributes one per line, e.g "Work type: Development"
+fn format_work_attributes(
+    attributes: &[WorkAttributeValue],
+    attribute_names: &HashMap<String, String>,
+) -> String {
+    attributes
+        .iter()
+        .map(|attribute| {
+            format!(
+                "{}: {}",
+                attribute_names
+                    .get(&attribute.key)
+                    .unwrap_or(&attribute.key),
+                attribute.value
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Truncate by characters, commit subjects and calendar summaries aren't always ASCII
+pub fn truncate_string(string: &str, max_length: usize) -> String {
+    if string.chars().count() > max_length {
+        format!("{}...", string.chars().take(max_length).collect::<String>())
     } else {
         string.to_string()
     }
 }
 
-fn add_list_footer_row(builder: &mut Builder, total_time: i32) {
-    builder.push_record(vec![
-        format!("{}/8h", utils::format_duration(total_time)).as_str()
-    ]);
+fn add_list_footer_row(
+    builder: &mut Builder,
+    total_time: i32,
+    total_billable: i32,
+    warning: Option<String>,
+) {
+    let mut footer = format!(
+        "{}/8h, billable {}",
+        utils::format_duration(total_time),
+        utils::format_duration(total_billable)
+    );
+    if let Some(warning) = warning {
+        footer = format!("{}\n{}", footer, warning);
+    }
+
+    builder.push_record(vec![footer.as_str()]);
 }
 
 fn apply_list_table_formatting(table: &mut Table) {
-    table.modify(Rows::first(), Span::column(5));
-    table.modify(Rows::single(1), Span::column(5));
-    table.modify(Rows::last(), Span::column(5));
+    table.modify(Rows::first(), Span::column(7));
+    table.modify(Rows::single(1), Span::column(7));
+    table.modify(Rows::last(), Span::column(7));
 
     table.modify(Rows::first(), Alignment::center());
     table.modify(Rows::single(1), Alignment::center());
@@ -173,23 +358,13 @@ fn filter_out_worklogs_by_date<'a>(
 mod tests {
     use super::*;
 
-    use crate::models::{JiraIssue, TempoIssue, UserCredentials, WorklogItem};
-
-    const TEST_DB_PATH: &str = "test_build_list_table";
+    use crate::api::create_test_storage;
+    use crate::models::{
+        JiraIssue, TempoIssue, WorkAttributeValue, WorklogAttributes, WorklogItem,
+    };
 
     fn init_test_db(path: &str) -> Storage {
-        cleanup_test_db(path);
-
-        let storage = Storage::with_path(TEST_DB_PATH);
-        storage.store_credentials(UserCredentials {
-            url: "https://test.atlassian.net".to_string(),
-            account_id: "test123".to_string(),
-            tempo_token: "test-tempo-token".to_string(),
-            jira_token: "test-jira-token".to_string(),
-            jira_email: "test@example.com".to_string(),
-        });
-
-        storage
+        create_test_storage(path, "https://test.atlassian.net")
     }
 
     fn cleanup_test_db(path: &str) {
@@ -202,18 +377,36 @@ mod tests {
         let worklogs = vec![WorklogItem {
             tempo_worklog_id: 99,
             time_spent_seconds: 3600,
+            billable_seconds: 3600,
             description: "Test comment".to_string(),
+            start_date: "2025-04-01".to_string(),
+            start_time: "09:00:00".to_string(),
             created_at: "2025-04-01T00:00:00Z".to_string(),
+            updated_at: String::new(),
             issue: TempoIssue { id: 123 },
+            author: None,
+            attributes: WorklogAttributes {
+                values: vec![WorkAttributeValue {
+                    key: "_WorkType_".to_string(),
+                    value: "Development".to_string(),
+                }],
+            },
             jira_issue: Some(JiraIssue {
                 id: "123".to_string(),
                 key: "TEST-123".to_string(),
+                fields: None,
             }),
         }];
 
         let storage = init_test_db(test_db_path);
 
-        let table = build_list_table(worklogs, &"2025-04-01".to_string(), &storage);
+        let table = build_list_table(
+            worklogs,
+            &"2025-04-01".to_string(),
+            Some("timesheet approved by Jane Doe".to_string()),
+            &storage,
+            true,
+        );
         let table_str = table.to_string();
 
         assert!(table_str.contains("ID"));
@@ -225,24 +418,120 @@ mod tests {
         assert!(table_str.contains("1h"));
         assert!(table_str.contains("123"));
         assert!(table_str.contains("Test comment"));
+        assert!(table_str.contains("_WorkType_: Development"));
         assert!(table_str.contains("https://test.atlassian.net/browse/TEST-123"));
 
-        assert!(table_str.contains("1h/8h"));
+        assert!(table_str.contains("Billable"));
+        assert!(table_str.contains("1h/8h, billable 1h"));
+        assert!(table_str.contains("(2025-04-01), timesheet approved by Jane Doe"));
 
         cleanup_test_db(test_db_path);
     }
 
+    #[test]
+    fn test_queued_changes() {
+        let test_db_path = "test_list_queued_changes";
+        let storage = init_test_db(test_db_path);
+        let worklog = NewWorklog {
+            issue_key: "TEST-123".to_string(),
+            time_spent_seconds: 3600,
+            description: "Review".to_string(),
+            start_date: "2025-04-01".to_string(),
+            start_time: None,
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        };
+        crate::commands::sync::queue_worklogs(&storage, &[worklog], None, false);
+
+        let (_, pending) = queued_changes(&storage, "2025-04-01", "2025-04-30", true);
+        assert_eq!(pending.len(), 1);
+
+        // Listing another user's worklogs
+        let (labels, pending) = queued_changes(&storage, "2025-04-01", "2025-04-30", false);
+        assert!(labels.is_empty());
+        assert!(pending.is_empty());
+
+        drop(storage);
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_inaccessible_issue_rows() {
+        let test_db_path = "test_list_inaccessible_issue_rows";
+        let storage = init_test_db(test_db_path);
+        storage.store_inaccessible_issue("123", 0);
+        storage.store_inaccessible_issue("456", 0);
+        let worklog = |issue_id: i64| WorklogItem {
+            tempo_worklog_id: issue_id + 1,
+            time_spent_seconds: 3600,
+            billable_seconds: 3600,
+            description: "Test comment".to_string(),
+            start_date: "2025-04-01".to_string(),
+            start_time: "09:00:00".to_string(),
+            created_at: "2025-04-01T00:00:00Z".to_string(),
+            updated_at: String::new(),
+            issue: TempoIssue { id: issue_id },
+            author: None,
+            attributes: WorklogAttributes::default(),
+            jira_issue: None,
+        };
+        // Issue 789 isn't cached while offline
+        let worklogs = [worklog(456), worklog(123), worklog(456), worklog(789)];
+        let worklogs: Vec<&WorklogItem> = worklogs.iter().collect();
+        let config = UserCredentials {
+            url: "https://test.atlassian.net".to_string(),
+            account_id: "test123".to_string(),
+            tempo_token: "test-tempo-token".to_string(),
+            jira_token: "test-jira-token".to_string(),
+            jira_email: "test@example.com".to_string(),
+        };
+
+        let mut builder = Builder::default();
+        let (mut total_time, mut total_billable) = (0, 0);
+        add_list_worklog_rows(
+            &mut builder,
+            &worklogs,
+            &config,
+            &storage,
+            &HashMap::new(),
+            &HashMap::new(),
+            &mut total_time,
+            &mut total_billable,
+        );
+
+        let table = builder.build().to_string();
+        assert!(table.contains("456 (inaccessible)"));
+        assert!(table.contains("789") && !table.contains("789 (inaccessible)"));
+        assert_eq!(total_time, 4 * 3600);
+        assert_eq!(
+            inaccessible_issues_warning(&worklogs, &storage).as_deref(),
+            Some("Warning. Issue(s) deleted or not visible to you: 123, 456")
+        );
+        assert_eq!(inaccessible_issues_warning(&[], &storage), None);
+
+        drop(storage);
+        cleanup_test_db(test_db_path);
+    }
+
     #[tokio::test]
     async fn test_filter_out_worklogs_by_date() {
         let worklogs = vec![WorklogItem {
             tempo_worklog_id: 99,
             time_spent_seconds: 3600,
+            billable_seconds: 3600,
             description: "Test comment".to_string(),
+            start_date: "2025-04-01".to_string(),
+            start_time: "09:00:00".to_string(),
             created_at: "2025-04-01T00:00:00Z".to_string(),
+            updated_at: String::new(),
             issue: TempoIssue { id: 123 },
+            author: None,
+            attributes: WorklogAttributes::default(),
             jira_issue: Some(JiraIssue {
                 id: "123".to_string(),
                 key: "TEST-123".to_string(),
+                fields: None,
             }),
         }];
 
@@ -260,23 +549,37 @@ mod tests {
             WorklogItem {
                 tempo_worklog_id: 99,
                 time_spent_seconds: 3600,
+                billable_seconds: 3600,
                 description: "Test comment".to_string(),
+                start_date: "2025-04-01".to_string(),
+                start_time: "09:00:00".to_string(),
                 created_at: "2025-04-01T00:00:00Z".to_string(),
+                updated_at: String::new(),
                 issue: TempoIssue { id: 123 },
+                author: None,
+                attributes: WorklogAttributes::default(),
                 jira_issue: Some(JiraIssue {
                     id: "123".to_string(),
                     key: "TEST-123".to_string(),
+                    fields: None,
                 }),
             },
             WorklogItem {
                 tempo_worklog_id: 100,
                 time_spent_seconds: 7200,
+                billable_seconds: 7200,
                 description: "Test comment 2".to_string(),
+                start_date: "2025-04-01".to_string(),
+                start_time: "10:00:00".to_string(),
                 created_at: "2025-04-01T00:00:00Z".to_string(),
+                updated_at: String::new(),
                 issue: TempoIssue { id: 123 },
+                author: None,
+                attributes: WorklogAttributes::default(),
                 jira_issue: Some(JiraIssue {
                     id: "123".to_string(),
                     key: "TEST-123".to_string(),
+                    fields: None,
                 }),
             },
         ];
@@ -290,4 +593,11 @@ mod tests {
         assert_eq!(truncate_string("Hello, world!", 10), "Hello, wor...");
         assert_eq!(truncate_string("Hello, world!", 15), "Hello, world!");
     }
+
+    #[test]
+    fn test_truncate_multibyte_string() {
+        assert_eq!(truncate_string("Réunion d'équipe", 3), "Réu...");
+        assert_eq!(truncate_string("日本語のコミット", 3), "日本語...");
+        assert_eq!(truncate_string("日本語", 3), "日本語");
+    }
 }
@@ -1,4 +1,6 @@
 use crate::api::{ApiClient, ApiTrait};
+use crate::commands::list::{queued_changes, resolve_account_id};
+use crate::commands::mirror::{mirrored_worklogs, MirrorArgs};
 use crate::models::WorklogItem;
 use crate::storage::Storage;
 use crate::utils;
@@ -11,14 +13,36 @@ use tabled::{
     Table,
 };
 
-pub async fn list_range(api: &ApiClient, date_from: &str, date_to: &str) {
+pub async fn list_range(
+    api: &ApiClient,
+    date_from: &str,
+    date_to: &str,
+    user: Option<&str>,
+    mirror_args: &MirrorArgs,
+) {
     let mut spinner = Spinner::new(Spinners::Dots, "Retrieving worklogs...".to_string());
 
-    match api.list_worklogs(&date_from, &date_to).await {
+    let account_id = match resolve_account_id(api, user).await {
+        Ok(account_id) => account_id,
+        Err(e) => {
+            spinner.stop_with_message(format!("\nError. {}", e));
+            return;
+        }
+    };
+
+    let worklogs = match user {
+        None => mirrored_worklogs(api, date_from, date_to, mirror_args).await,
+        Some(_) => {
+            api.list_user_worklogs(&account_id, date_from, date_to)
+                .await
+        }
+    };
+
+    match worklogs {
         Ok(worklogs) => {
             spinner.stop_with_message(format!(
                 "\n{}",
-                build_range_table(worklogs, &date_from, &date_to, &api.storage)
+                build_range_table(worklogs, &date_from, &date_to, &api.storage, user.is_none())
             ));
         }
         Err(e) => {
@@ -32,10 +56,12 @@ fn build_range_table(
     date_from: &str,
     date_to: &str,
     storage: &Storage,
+    own: bool,
 ) -> Table {
     let config = storage.get_credentials().unwrap();
     let mut builder = Builder::default();
     let mut total_time = 0;
+    let mut total_billable = 0;
 
     builder.push_record(vec![
         format!("Worklogs from {} to {}", date_from, date_to).as_str()
@@ -43,16 +69,38 @@ fn build_range_table(
 
     crate::commands::list::add_column_headers(&mut builder);
 
+    let attribute_names = crate::commands::list::work_attribute_names(storage);
+    let (labels, pending) = queued_changes(storage, date_from, date_to, own);
     crate::commands::list::add_list_worklog_rows(
         &mut builder,
         &worklogs.iter().collect::<Vec<_>>(),
         &config,
+        storage,
+        &attribute_names,
+        &labels,
         &mut total_time,
+        &mut total_billable,
+    );
+    crate::commands::list::add_pending_worklog_rows(
+        &mut builder,
+        &pending,
+        &config,
+        &attribute_names,
     );
 
-    builder.push_record(vec![
-        format!("{}", utils::format_duration(total_time)).as_str()
-    ]);
+    let mut footer = format!(
+        "{}, billable {}",
+        utils::format_duration(total_time),
+        utils::format_duration(total_billable)
+    );
+    if let Some(warning) = crate::commands::list::inaccessible_issues_warning(
+        &worklogs.iter().collect::<Vec<_>>(),
+        storage,
+    ) {
+        footer = format!("{}\n{}", footer, warning);
+    }
+
+    builder.push_record(vec![footer.as_str()]);
 
     let mut table = builder.build();
     apply_range_table_formatting(&mut table);
@@ -61,8 +109,8 @@ fn build_range_table(
 }
 
 fn apply_range_table_formatting(table: &mut Table) {
-    table.modify(Rows::first(), Span::column(5));
-    table.modify(Rows::last(), Span::column(5));
+    table.modify(Rows::first(), Span::column(7));
+    table.modify(Rows::last(), Span::column(7));
 
     table.modify(Rows::first(), Alignment::center());
     table.modify(Rows::last(), Alignment::right());
@@ -1,22 +1,831 @@
-use crate::api::{ApiTrait, ApiClient};
+use crate::api::{is_offline_error, ApiClient, ApiTrait};
+use crate::commands::accounts::{account_attribute, resolve_accounts};
+use crate::commands::create::{
+    apply_billable_policy, create_worklogs, keep_original_duration, print_warnings, CreateOptions,
+    WorklogArgs,
+};
+use crate::commands::issue_log::format_estimate;
+use crate::commands::search::pick_issue;
+use crate::commands::sync::queue_worklogs;
+use crate::git;
+use crate::models::{NewWorklog, PendingCreateOptions, WorkAttributeValue};
+use crate::settings::{DurationSettings, RoundingSettings};
+use crate::storage::Storage;
+use crate::utils::{
+    format_duration, parse_duration_from_string, round_duration, split_duration, today_as_iso8601,
+};
+use crate::validators;
 use spinners::{Spinner, Spinners};
+use std::collections::{HashMap, HashSet};
+use std::io::IsTerminal;
+use std::path::Path;
+
+const RECENT_COMMITS: usize = 10;
+
+// How the remaining estimate of the issues is adjusted
+#[derive(clap::Args, Debug, Default)]
+pub struct RemainingArgs {
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = validators::validate_duration,
+        conflicts_with_all = ["reduce_remaining", "keep_remaining"],
+        help = "Set the remaining estimate of the issue (e.g., 2h)"
+    )]
+    pub remaining: Option<String>,
+    #[arg(
+        long,
+        conflicts_with = "keep_remaining",
+        help = "Reduce the remaining estimate by the time spent"
+    )]
+    pub reduce_remaining: bool,
+    #[arg(long, help = "Leave the remaining estimate unchanged")]
+    pub keep_remaining: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RemainingEstimate {
+    // Left to Jira
+    Auto,
+    Set(i32),
+    Reduce,
+    Keep,
+}
+
+fn remaining_mode(
+    options: &PendingCreateOptions,
+    durations: &DurationSettings,
+) -> Result<RemainingEstimate, String> {
+    match &options.remaining {
+        Some(remaining) => {
+            parse_duration_from_string(remaining, durations).map(RemainingEstimate::Set)
+        }
+        None if options.reduce_remaining => Ok(RemainingEstimate::Reduce),
+        None if options.keep_remaining => Ok(RemainingEstimate::Keep),
+        None => Ok(RemainingEstimate::Auto),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn log_time(
+    api: &ApiClient,
+    issue_key: Option<String>,
+    split: Option<String>,
+    time_spent: Option<String>,
+    comment: Option<String>,
+    from_commits: bool,
+    worklog_args: &WorklogArgs,
+    remaining_args: &RemainingArgs,
+    options: &CreateOptions,
+) {
+    let (issue_key, time_spent, comment) = shift_arguments(
+        &api.storage,
+        &api.settings.durations,
+        issue_key,
+        time_spent,
+        comment,
+    );
+
+    // Only known to be the time spent once shifted, check it before looking for the issue
+    if let Some(Err(e)) = time_spent
+        .as_deref()
+        .map(|time_spent| parse_duration_from_string(time_spent, &api.settings.durations))
+    {
+        eprintln!("Error. {}", e);
+        return;
+    }
+
+    let (issue_spec, split) = match (split, issue_key) {
+        (Some(_), Some(issue_key)) => {
+            eprintln!(
+                "Error. Give the issues either with --split or as an argument, not both: '{}'",
+                issue_key
+            );
+            return;
+        }
+        (Some(split), None) => (split, true),
+        (None, Some(issue_key)) => (issue_key, false),
+        (None, None) => match infer_issue_key(api, from_commits).await {
+            Ok(issue_key) => {
+                println!("Using issue {} found in git", issue_key);
+                (issue_key, false)
+            }
+            // Fall back to the picker when there's someone to pick
+            Err(_) if std::io::stdin().is_terminal() => match pick_issue(api).await {
+                Ok(issue_key) => (issue_key, false),
+                Err(e) => {
+                    eprintln!("Error. {}", e);
+                    return;
+                }
+            },
+            Err(e) => {
+                eprintln!("Error. {}", e);
+                return;
+            }
+        },
+    };
+
+    let worklogs = match worklog_args
+        .billable_seconds(&api.settings.durations)
+        .and_then(|billable_seconds| {
+            build_worklogs(
+                &api.storage,
+                &api.settings.durations,
+                &issue_spec,
+                split,
+                time_spent,
+                comment,
+                billable_seconds,
+                options.rounding(&api.settings.rounding),
+            )
+        }) {
+        Ok(worklogs) => worklogs,
+        Err(e) => {
+            eprintln!("Error. {}", e);
+            return;
+        }
+    };
+
+    let unresolved = apply_billable_policy(worklogs, &api.storage, &api.settings.billable);
+    let pending_options = PendingCreateOptions {
+        attributes: worklog_args.attributes.clone(),
+        account: worklog_args.account.clone(),
+        remaining: remaining_args.remaining.clone(),
+        reduce_remaining: remaining_args.reduce_remaining,
+        keep_remaining: remaining_args.keep_remaining,
+    };
+
+    // Without network the worklogs are queued as given, and their options resolved by `sync`
+    let worklogs = match resolve_worklogs(api, unresolved.clone(), &pending_options).await {
+        Ok(worklogs) => worklogs,
+        Err(e) if is_offline_error(&e) => {
+            println!(
+                "{}",
+                queue_worklogs(
+                    &api.storage,
+                    &unresolved,
+                    Some(pending_options),
+                    options.force
+                )
+            );
+            return;
+        }
+        Err(e) => {
+            eprintln!("Error. {}", e);
+            return;
+        }
+    };
 
-pub async fn log_time(api: &ApiClient, issue_key: &str, time_spent: &str, comment: Option<String>) {
     let mut spinner = Spinner::new(Spinners::Dots, "Logging time...".to_string());
 
-    match api.log_time(issue_key, time_spent, comment).await {
-        Ok(worklog) => {
+    match create_worklogs(api, &worklogs, options).await {
+        Ok(report) if report.created.is_empty() => {
+            spinner.stop_with_message("\nNothing logged".to_string());
+            print_warnings(&report.warnings);
+        }
+        Ok(report) => {
+            let logged: Vec<String> = report
+                .created
+                .iter()
+                .map(|(w, _)| {
+                    format!(
+                        "{} ({})",
+                        w.issue_key,
+                        format_duration(w.time_spent_seconds)
+                    )
+                })
+                .collect();
+            let ids: Vec<String> = report
+                .created
+                .iter()
+                .map(|(_, w)| w.tempo_worklog_id.to_string())
+                .collect();
+
             spinner.stop_with_message(format!(
-                "\nTime logged successfully on {}, {}",
-                issue_key, time_spent
+                "\nTime logged successfully on {}",
+                logged.join(", ")
             ));
+            print_warnings(&report.warnings);
             println!(
-                "Run 'tempie delete {}' to delete it",
-                worklog.tempo_worklog_id
+                "Run 'tempie delete {}' to delete {}",
+                ids.join(" "),
+                if ids.len() > 1 { "them" } else { "it" }
             );
+            print_estimates(api, &worklogs).await;
+        }
+        Err(e) if is_offline_error(&e.message) => {
+            // Worklogs created before the network went down are kept, only the rest is queued
+            let left: Vec<NewWorklog> = e
+                .not_created(&worklogs)
+                .into_iter()
+                .map(|i| unresolved[i].clone())
+                .collect();
+            spinner.stop_with_message(format!(
+                "\n{}\n{}",
+                e,
+                queue_worklogs(&api.storage, &left, Some(pending_options), options.force)
+            ));
         }
         Err(e) => {
             spinner.stop_with_message(format!("\nError. Failed to log time: {}", e));
         }
     }
 }
+
+// Resolve the account, work attributes and remaining estimate of each worklog
+pub async fn resolve_worklogs(
+    api: &ApiClient,
+    worklogs: Vec<NewWorklog>,
+    options: &PendingCreateOptions,
+) -> Result<Vec<NewWorklog>, String> {
+    let mode = remaining_mode(options, &api.settings.durations)?;
+    let worklogs = set_attributes(api, worklogs, options).await?;
+
+    set_remaining_estimates(api, worklogs, mode).await
+}
+
+// Set the account and the work attributes of each worklog. The accounts are resolved once
+// per project
+async fn set_attributes(
+    api: &ApiClient,
+    worklogs: Vec<NewWorklog>,
+    options: &PendingCreateOptions,
+) -> Result<Vec<NewWorklog>, String> {
+    let worklog_args = WorklogArgs {
+        attributes: options.attributes.clone(),
+        account: options.account.clone(),
+        ..WorklogArgs::default()
+    };
+    let issue_keys: Vec<String> = worklogs.iter().map(|w| w.issue_key.clone()).collect();
+    let accounts = resolve_accounts(api, &issue_keys, worklog_args.account.as_deref()).await?;
+    let mut result = Vec::new();
+
+    for mut worklog in worklogs {
+        let account = accounts.get(&worklog.issue_key).cloned().flatten();
+        let mut current: Vec<WorkAttributeValue> = worklog.attributes.clone();
+        current.extend(account.iter().map(account_attribute));
+
+        worklog.attributes = worklog_args.work_attributes(api, &current).await?;
+        result.push(worklog);
+    }
+
+    Ok(result)
+}
+
+// The issue key is optional, so clap puts the time spent into its place when it's omitted,
+// e.g `tempie log 1h "comment"`. Shift the arguments back then. "." stands for no issue key
+fn shift_arguments(
+    storage: &Storage,
+    durations: &DurationSettings,
+    issue_key: Option<String>,
+    time_spent: Option<String>,
+    comment: Option<String>,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let is_duration = |value: &str| {
+        storage.get_alias(value).is_none() && parse_duration_from_string(value, durations).is_ok()
+    };
+
+    match issue_key {
+        Some(issue_key) if issue_key == "." => (None, time_spent, comment),
+        Some(issue_key)
+            if comment.is_none()
+                && is_duration(&issue_key)
+                && !time_spent.as_deref().is_some_and(is_duration) =>
+        {
+            (None, Some(issue_key), time_spent)
+        }
+        issue_key => (issue_key, time_spent, comment),
+    }
+}
+
+// Infer the issue key from the current git branch and, optionally, the recent commit messages.
+// The first key that exists in Jira wins
+async fn infer_issue_key(api: &ApiClient, from_commits: bool) -> Result<String, String> {
+    let commits = if from_commits { RECENT_COMMITS } else { 0 };
+
+    for candidate in git::find_issue_key_candidates(Path::new("."), commits) {
+        if let Ok(issue) = api.get_jira_issue(&candidate).await {
+            return Ok(issue.key);
+        }
+    }
+
+    Err("No issue key given and none found in the current git branch".to_string())
+}
+
+// Build one worklog per issue, splitting the time spent and billable time between them.
+// The total is rounded before it's split, so the parts still add up to it. A single alias
+// fills in the missing duration and comment with its defaults. Aliases are expanded later,
+// along with their billable policy
+#[allow(clippy::too_many_arguments)]
+fn build_worklogs(
+    storage: &Storage,
+    durations: &DurationSettings,
+    issue_spec: &str,
+    split: bool,
+    time_spent: Option<String>,
+    comment: Option<String>,
+    billable_seconds: Option<i32>,
+    rounding: Option<&RoundingSettings>,
+) -> Result<Vec<NewWorklog>, String> {
+    let issues = parse_issue_spec(issue_spec, split)?;
+    let alias = match issues.as_slice() {
+        [(issue_key, _)] => storage.get_alias(issue_key),
+        _ => None,
+    };
+
+    let time_spent = time_spent
+        .or_else(|| alias.as_ref().and_then(|a| a.duration.clone()))
+        .ok_or("The time spent is required")?;
+    let description = comment
+        .or_else(|| alias.as_ref().and_then(|a| a.description.clone()))
+        .unwrap_or_default();
+
+    let weights: Vec<u32> = issues.iter().map(|(_, weight)| *weight).collect();
+    let total = parse_duration_from_string(&time_spent, durations)?;
+    let rounded = rounding.map_or(total, |rounding| round_duration(total, rounding));
+    let original_parts = split_duration(total, &weights);
+    let parts = split_duration(rounded, &weights);
+    let billable_parts: Vec<Option<i32>> = match billable_seconds {
+        Some(billable_seconds) => split_duration(billable_seconds, &weights)
+            .into_iter()
+            .map(Some)
+            .collect(),
+        None => vec![None; weights.len()],
+    };
+
+    if parts.contains(&0) {
+        return Err(format!(
+            "{} is too short to split between {} issues",
+            time_spent,
+            parts.len()
+        ));
+    }
+
+    Ok(issues
+        .into_iter()
+        .zip(parts)
+        .zip(billable_parts)
+        .zip(original_parts)
+        .map(
+            |((((issue_key, _), time_spent_seconds), billable_seconds), original)| {
+                let mut worklog = NewWorklog {
+                    issue_key,
+                    time_spent_seconds,
+                    description: description.clone(),
+                    start_date: today_as_iso8601(),
+                    start_time: None,
+                    billable_seconds,
+                    attributes: Vec::new(),
+                    remaining_estimate_seconds: None,
+                };
+
+                if let Some(rounding) = rounding {
+                    keep_original_duration(&mut worklog, original, rounding);
+                }
+                worklog
+            },
+        )
+        .collect())
+}
+
+// Parse a comma separated list of issue keys, e.g "ABC-1,ABC-2" -> equal parts.
+// With `split` every key needs a percentage, e.g "ABC-1:70%,ABC-2:30%"
+fn parse_issue_spec(issue_spec: &str, split: bool) -> Result<Vec<(String, u32)>, String> {
+    let mut issues: Vec<(String, u32)> = Vec::new();
+
+    for entry in issue_spec.split(',').map(str::trim) {
+        let (issue_key, weight) = match entry.split_once(':') {
+            Some((issue_key, percent)) if split => {
+                let weight = percent
+                    .trim()
+                    .trim_end_matches('%')
+                    .parse::<u32>()
+                    .map_err(|_| format!("Invalid percentage for {}: '{}'", issue_key, percent))?;
+                (issue_key.trim(), weight)
+            }
+            Some(_) => return Err("Use --split to log weighted parts".to_string()),
+            None if split => return Err(format!("Missing percentage for {}", entry)),
+            None => (entry, 1),
+        };
+
+        if issue_key.is_empty() {
+            return Err(format!("Invalid issue list: '{}'", issue_spec));
+        }
+
+        if issues.iter().any(|(key, _)| key == issue_key) {
+            return Err(format!("Issue {} is listed more than once", issue_key));
+        }
+
+        issues.push((issue_key.to_string(), weight));
+    }
+
+    if split && issues.iter().map(|(_, weight)| weight).sum::<u32>() != 100 {
+        return Err("Split percentages must add up to 100%".to_string());
+    }
+
+    Ok(issues)
+}
+
+// Fill in the remaining estimate to send with each worklog
+async fn set_remaining_estimates(
+    api: &ApiClient,
+    worklogs: Vec<NewWorklog>,
+    mode: RemainingEstimate,
+) -> Result<Vec<NewWorklog>, String> {
+    let mut current = HashMap::new();
+
+    if matches!(mode, RemainingEstimate::Reduce | RemainingEstimate::Keep) {
+        for worklog in &worklogs {
+            if !current.contains_key(&worklog.issue_key) {
+                let estimate = api.get_time_tracking(&worklog.issue_key).await?;
+                current.insert(
+                    worklog.issue_key.clone(),
+                    estimate.remaining_estimate_seconds,
+                );
+            }
+        }
+    }
+
+    Ok(apply_remaining_estimates(worklogs, mode, &current))
+}
+
+// Several worklogs on the same issue reduce its remaining estimate one after the other.
+// Issues without an estimate are left to Jira
+fn apply_remaining_estimates(
+    worklogs: Vec<NewWorklog>,
+    mode: RemainingEstimate,
+    current: &HashMap<String, Option<i32>>,
+) -> Vec<NewWorklog> {
+    let mut remaining = current.clone();
+
+    worklogs
+        .into_iter()
+        .map(|mut worklog| {
+            worklog.remaining_estimate_seconds = match mode {
+                RemainingEstimate::Auto => None,
+                RemainingEstimate::Set(seconds) => Some(seconds),
+                RemainingEstimate::Keep => remaining.get(&worklog.issue_key).copied().flatten(),
+                RemainingEstimate::Reduce => {
+                    let reduced = remaining
+                        .get(&worklog.issue_key)
+                        .copied()
+                        .flatten()
+                        .map(|seconds| (seconds - worklog.time_spent_seconds).max(0));
+                    remaining.insert(worklog.issue_key.clone(), reduced);
+                    reduced
+                }
+            };
+            worklog
+        })
+        .collect()
+}
+
+// Show the estimates of the issues time was logged on, to spot the ones over budget
+async fn print_estimates(api: &ApiClient, worklogs: &[NewWorklog]) {
+    let mut seen = HashSet::new();
+    let issue_keys: Vec<&String> = worklogs
+        .iter()
+        .map(|w| &w.issue_key)
+        .filter(|issue_key| seen.insert(*issue_key))
+        .collect();
+
+    for issue_key in issue_keys {
+        if let Ok(estimate) = api.get_time_tracking(issue_key).await {
+            println!(
+                "{}: {}",
+                issue_key,
+                format_estimate(estimate.time_spent_seconds.unwrap_or(0), &estimate)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Alias;
+    use crate::settings::{KeepOriginal, RoundingMode};
+
+    #[test]
+    fn test_shift_arguments() {
+        let test_db_path = "test_shift_arguments";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+        let durations = DurationSettings::default();
+        let some = |value: &str| Some(value.to_string());
+
+        assert_eq!(
+            shift_arguments(
+                &storage,
+                &durations,
+                some("ABC-1"),
+                some("1h"),
+                some("Comment")
+            ),
+            (some("ABC-1"), some("1h"), some("Comment"))
+        );
+        assert_eq!(
+            shift_arguments(&storage, &durations, some("."), some("1h"), some("Comment")),
+            (None, some("1h"), some("Comment"))
+        );
+        assert_eq!(
+            shift_arguments(&storage, &durations, some("1h"), some("Comment"), None),
+            (None, some("1h"), some("Comment"))
+        );
+        assert_eq!(
+            shift_arguments(&storage, &durations, some("1h"), None, None),
+            (None, some("1h"), None)
+        );
+        assert_eq!(
+            shift_arguments(&storage, &durations, None, None, None),
+            (None, None, None)
+        );
+
+        // Aliases are never mistaken for the time spent
+        storage.store_alias(&Alias {
+            name: "1h".to_string(),
+            issue_key: "ABC-1".to_string(),
+            description: None,
+            duration: None,
+            billable: None,
+        });
+        assert_eq!(
+            shift_arguments(&storage, &durations, some("1h"), some("30m"), None),
+            (some("1h"), some("30m"), None)
+        );
+
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_parse_issue_spec() {
+        assert_eq!(
+            parse_issue_spec("ABC-1", false),
+            Ok(vec![("ABC-1".to_string(), 1)])
+        );
+        assert_eq!(
+            parse_issue_spec("ABC-1, ABC-2,ABC-3", false),
+            Ok(vec![
+                ("ABC-1".to_string(), 1),
+                ("ABC-2".to_string(), 1),
+                ("ABC-3".to_string(), 1)
+            ])
+        );
+        assert_eq!(
+            parse_issue_spec("ABC-1:50%,ABC-2:30%,ABC-3:20", true),
+            Ok(vec![
+                ("ABC-1".to_string(), 50),
+                ("ABC-2".to_string(), 30),
+                ("ABC-3".to_string(), 20)
+            ])
+        );
+
+        assert!(parse_issue_spec("ABC-1:50%,ABC-2:50%", false).is_err());
+        assert!(parse_issue_spec("ABC-1:50%,ABC-2", true).is_err());
+        assert!(parse_issue_spec("ABC-1:50%,ABC-2:40%", true).is_err());
+        assert!(parse_issue_spec("ABC-1:half,ABC-2:50%", true).is_err());
+        assert!(parse_issue_spec("ABC-1,ABC-1", false).is_err());
+        assert!(parse_issue_spec("ABC-1,", false).is_err());
+    }
+
+    fn cleanup_test_db(path: &str) {
+        let _ = std::fs::remove_dir_all(path);
+    }
+
+    #[test]
+    fn test_build_worklogs() {
+        let test_db_path = "test_build_worklogs";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+        let durations = DurationSettings::default();
+
+        let worklogs = build_worklogs(
+            &storage,
+            &durations,
+            "ABC-1:50%,ABC-2:30%,ABC-3:20%",
+            true,
+            Some("3h".to_string()),
+            Some("sync".to_string()),
+            Some(5400),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(worklogs.len(), 3);
+        assert_eq!(worklogs[0].issue_key, "ABC-1");
+        assert_eq!(worklogs[0].time_spent_seconds, 5400);
+        assert_eq!(worklogs[1].time_spent_seconds, 3240);
+        assert_eq!(worklogs[2].time_spent_seconds, 2160);
+        assert!(worklogs.iter().all(|w| w.description == "sync"));
+        let billable: Vec<Option<i32>> = worklogs.iter().map(|w| w.billable_seconds).collect();
+        assert_eq!(billable, vec![Some(2700), Some(1620), Some(1080)]);
+
+        assert!(
+            build_worklogs(&storage, &durations, "ABC-1", false, None, None, None, None).is_err()
+        );
+
+        // Every issue has to get some time
+        assert!(build_worklogs(
+            &storage,
+            &durations,
+            "ABC-1:99%,ABC-2:1%",
+            true,
+            Some("30m".to_string()),
+            None,
+            None,
+            None
+        )
+        .is_err());
+
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_build_worklogs_rounds_the_total() {
+        let test_db_path = "test_build_worklogs_rounds_the_total";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+        let durations = DurationSettings::default();
+        let rounding = |mode| RoundingSettings {
+            mode,
+            keep_original: KeepOriginal::Description,
+            ..RoundingSettings::default()
+        };
+        let build = |time_spent: &str, rounding: &RoundingSettings| {
+            build_worklogs(
+                &storage,
+                &durations,
+                "ABC-1,ABC-2,ABC-3",
+                false,
+                Some(time_spent.to_string()),
+                None,
+                None,
+                Some(rounding),
+            )
+            .unwrap()
+        };
+        let seconds = |worklogs: Vec<NewWorklog>| -> Vec<i32> {
+            worklogs.iter().map(|w| w.time_spent_seconds).collect()
+        };
+
+        // The parts add up to the rounded total, not to the rounded parts
+        assert_eq!(
+            seconds(build("1h", &rounding(RoundingMode::Nearest))),
+            vec![1200, 1200, 1200]
+        );
+        assert_eq!(
+            seconds(build("1h", &rounding(RoundingMode::Up))),
+            vec![1200, 1200, 1200]
+        );
+
+        let worklogs = build("50m", &rounding(RoundingMode::Nearest));
+        assert_eq!(
+            worklogs.iter().map(|w| w.time_spent_seconds).sum::<i32>(),
+            2700
+        );
+        assert_eq!(worklogs[0].description, "Original: 17m");
+
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_build_worklogs_with_alias() {
+        let test_db_path = "test_build_worklogs_with_alias";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+        let durations = DurationSettings::default();
+
+        storage.store_alias(&Alias {
+            name: "standup".to_string(),
+            issue_key: "INTERNAL-4821".to_string(),
+            description: Some("Daily standup".to_string()),
+            duration: Some("15m".to_string()),
+            billable: Some(false),
+        });
+
+        let worklogs = build_worklogs(
+            &storage, &durations, "standup", false, None, None, None, None,
+        )
+        .unwrap();
+        assert_eq!(worklogs[0].issue_key, "standup");
+        assert_eq!(worklogs[0].time_spent_seconds, 900);
+        assert_eq!(worklogs[0].description, "Daily standup");
+
+        let worklogs = build_worklogs(
+            &storage,
+            &durations,
+            "standup",
+            false,
+            Some("30m".to_string()),
+            Some("Planning".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(worklogs[0].time_spent_seconds, 1800);
+        assert_eq!(worklogs[0].description, "Planning");
+
+        // Defaults only apply to a single alias, but aliases are accepted in lists too
+        assert!(build_worklogs(
+            &storage,
+            &durations,
+            "standup,ABC-1",
+            false,
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err());
+
+        let worklogs = build_worklogs(
+            &storage,
+            &durations,
+            "standup,ABC-1",
+            false,
+            Some("1h".to_string()),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(worklogs[0].issue_key, "standup");
+        assert_eq!(worklogs[1].issue_key, "ABC-1");
+        assert_eq!(worklogs[0].time_spent_seconds, 1800);
+
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_apply_remaining_estimates() {
+        let worklog = |issue_key: &str, seconds: i32| NewWorklog {
+            issue_key: issue_key.to_string(),
+            time_spent_seconds: seconds,
+            description: String::new(),
+            start_date: "2025-04-07".to_string(),
+            start_time: None,
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        };
+        let worklogs = || {
+            vec![
+                worklog("ABC-1", 3600),
+                worklog("ABC-1", 7200),
+                worklog("ABC-2", 3600),
+            ]
+        };
+        let current = HashMap::from([
+            ("ABC-1".to_string(), Some(4 * 3600)),
+            ("ABC-2".to_string(), None),
+        ]);
+        let remaining = |worklogs: Vec<NewWorklog>| -> Vec<Option<i32>> {
+            worklogs
+                .iter()
+                .map(|w| w.remaining_estimate_seconds)
+                .collect()
+        };
+
+        assert_eq!(
+            remaining(apply_remaining_estimates(
+                worklogs(),
+                RemainingEstimate::Auto,
+                &current
+            )),
+            vec![None, None, None]
+        );
+        assert_eq!(
+            remaining(apply_remaining_estimates(
+                worklogs(),
+                RemainingEstimate::Set(7200),
+                &current
+            )),
+            vec![Some(7200), Some(7200), Some(7200)]
+        );
+        assert_eq!(
+            remaining(apply_remaining_estimates(
+                worklogs(),
+                RemainingEstimate::Reduce,
+                &current
+            )),
+            vec![Some(3 * 3600), Some(3600), None]
+        );
+        assert_eq!(
+            remaining(apply_remaining_estimates(
+                worklogs(),
+                RemainingEstimate::Keep,
+                &current
+            )),
+            vec![Some(4 * 3600), Some(4 * 3600), None]
+        );
+
+        let options = PendingCreateOptions {
+            remaining: Some("1h30m".to_string()),
+            ..PendingCreateOptions::default()
+        };
+        assert_eq!(
+            remaining_mode(&options, &DurationSettings::default()),
+            Ok(RemainingEstimate::Set(5400))
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use crate::api::{ApiClient, ApiTrait};
+use crate::models::WorklogItem;
+use crate::utils::get_last_day_of_month;
+
+use chrono::{Datelike, Duration, Months, NaiveDate, Utc};
+
+// How fresh the local mirror of the worklogs has to be
+#[derive(clap::Args, Debug, Default)]
+pub struct MirrorArgs {
+    // Only the current user's worklogs are mirrored
+    #[arg(
+        long,
+        conflicts_with_all = ["offline", "user"],
+        help = "Fetch the worklogs from Tempo again instead of only the changed ones"
+    )]
+    pub refresh: bool,
+    #[arg(
+        long,
+        conflicts_with = "user",
+        help = "Only read the local copy of the worklogs, without asking Tempo"
+    )]
+    pub offline: bool,
+}
+
+// Get the current user's worklogs between the dates from the local mirror. Months that aren't
+// mirrored yet, or weren't fetched in full recently, are fetched in full. The others only get
+// the worklogs updated since
+pub async fn mirrored_worklogs(
+    api: &ApiClient,
+    date_from: &str,
+    date_to: &str,
+    args: &MirrorArgs,
+) -> Result<Vec<WorklogItem>, String> {
+    let months = months_between(date_from, date_to);
+
+    if args.offline {
+        if let Some(month) = months
+            .iter()
+            .find(|month| api.storage.get_mirror_refreshed_at(month).is_none())
+        {
+            return Err(format!(
+                "No local copy of the worklogs of {} yet, run without --offline",
+                month
+            ));
+        }
+    } else {
+        refresh_mirror(api, &months, args.refresh).await?;
+    }
+
+    let mut worklogs = api.storage.get_mirrored_worklogs(date_from, date_to);
+
+    if args.offline {
+        // Only the cached issues, the others are shown by their id
+        for worklog in worklogs.iter_mut() {
+            worklog.jira_issue = api.storage.get_jira_issue(&worklog.issue.id.to_string());
+        }
+    } else {
+        let _ = api.prefetch_jira_issues(&worklogs).await;
+        for worklog in worklogs.iter_mut() {
+            api.attach_jira_issue(worklog).await?;
+        }
+    }
+
+    Ok(worklogs)
+}
+
+async fn refresh_mirror(api: &ApiClient, months: &[String], full: bool) -> Result<(), String> {
+    // Taken before fetching, a minute early to allow for clock differences. Worklogs fetched
+    // twice are just stored again
+    let refreshed_at = (Utc::now() - Duration::minutes(1))
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string();
+    // Deleted worklogs, or ones moved to another month, only go away with a full fetch
+    let stale_before = (Utc::now() - Duration::minutes(api.settings.tempo.full_refresh_minutes))
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string();
+
+    let (mirrored, missing): (Vec<&String>, Vec<&String>) = months.iter().partition(|month| {
+        !full
+            && api.storage.get_mirror_refreshed_at(month).is_some()
+            && api
+                .storage
+                .get_mirror_fetched_at(month)
+                .is_some_and(|fetched_at| fetched_at > stale_before)
+    });
+
+    for month in missing {
+        let (first_day, last_day) = month_bounds(month);
+        let worklogs = api.list_worklogs(&first_day, &last_day).await?;
+
+        api.storage
+            .replace_mirrored_worklogs(&first_day, &last_day, &worklogs);
+        api.storage.store_mirror_refreshed_at(month, &refreshed_at);
+        api.storage.store_mirror_fetched_at(month, &refreshed_at);
+    }
+
+    let updated_from = mirrored
+        .iter()
+        .filter_map(|month| api.storage.get_mirror_refreshed_at(month))
+        .min();
+
+    if let (Some(first), Some(last), Some(updated_from)) =
+        (mirrored.first(), mirrored.last(), updated_from)
+    {
+        let (date_from, _) = month_bounds(first);
+        let (_, date_to) = month_bounds(last);
+        let worklogs = api
+            .list_worklogs_updated_from(&date_from, &date_to, &updated_from)
+            .await?;
+
+        for worklog in &worklogs {
+            api.storage.store_mirrored_worklog(worklog);
+        }
+        for month in mirrored {
+            api.storage.store_mirror_refreshed_at(month, &refreshed_at);
+        }
+    }
+
+    Ok(())
+}
+
+// The months the dates span, e.g ["2025-03", "2025-04"]
+fn months_between(date_from: &str, date_to: &str) -> Vec<String> {
+    let (Ok(from), Ok(to)) = (
+        NaiveDate::parse_from_str(date_from, "%Y-%m-%d"),
+        NaiveDate::parse_from_str(date_to, "%Y-%m-%d"),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut months = Vec::new();
+    let mut month = from.with_day0(0).unwrap();
+
+    while month <= to {
+        months.push(month.format("%Y-%m").to_string());
+        month = month + Months::new(1);
+    }
+
+    months
+}
+
+fn month_bounds(month: &str) -> (String, String) {
+    let first_day = format!("{}-01", month);
+    let last_day = get_last_day_of_month(&first_day);
+
+    (first_day, last_day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::create_test_api;
+    use mockito::Matcher;
+
+    fn worklog_json(id: i64, seconds: i32) -> String {
+        format!(
+            r#"{{"tempoWorklogId": {}, "timeSpentSeconds": {}, "description": "Review",
+                "startDate": "2025-04-07", "createdAt": "2025-04-07T10:00:00Z",
+                "issue": {{"id": 10001}}}}"#,
+            id, seconds
+        )
+    }
+
+    #[tokio::test]
+    async fn test_mirrored_worklogs() {
+        let test_db_path = "test_mirrored_worklogs";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_test_api(test_db_path, &server.url());
+
+        let full = server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(Matcher::Regex(
+                "^from=2025-04-01&to=2025-04-30&offset=0&limit=1000$".to_string(),
+            ))
+            .with_body(format!(r#"{{"results": [{}]}}"#, worklog_json(1, 3600)))
+            .expect(1)
+            .create_async()
+            .await;
+        let updated = server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(Matcher::Regex(
+                "updatedFrom=[^&]+&offset=0&limit=1000$".to_string(),
+            ))
+            .with_body(format!(
+                r#"{{"results": [{}, {}]}}"#,
+                worklog_json(1, 7200),
+                worklog_json(2, 1800)
+            ))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let args = MirrorArgs::default();
+        let seconds = |worklogs: Vec<WorklogItem>| -> Vec<(i64, i32)> {
+            worklogs
+                .iter()
+                .map(|w| (w.tempo_worklog_id, w.time_spent_seconds))
+                .collect()
+        };
+
+        // The month is fetched in full the first time, then only the updated worklogs
+        let worklogs = mirrored_worklogs(&api, "2025-04-01", "2025-04-30", &args).await;
+        assert_eq!(seconds(worklogs.unwrap()), vec![(1, 3600)]);
+        let worklogs = mirrored_worklogs(&api, "2025-04-01", "2025-04-30", &args).await;
+        assert_eq!(seconds(worklogs.unwrap()), vec![(1, 7200), (2, 1800)]);
+        full.assert_async().await;
+        updated.assert_async().await;
+
+        let offline = MirrorArgs {
+            refresh: false,
+            offline: true,
+        };
+        let worklogs = mirrored_worklogs(&api, "2025-04-07", "2025-04-07", &offline).await;
+        assert_eq!(worklogs.unwrap().len(), 2);
+        assert!(
+            mirrored_worklogs(&api, "2025-05-01", "2025-05-31", &offline)
+                .await
+                .is_err()
+        );
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    #[tokio::test]
+    async fn test_mirrored_worklogs_deleted_in_tempo() {
+        let test_db_path = "test_mirrored_worklogs_deleted";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_test_api(test_db_path, &server.url());
+        let args = MirrorArgs::default();
+        let ids = |worklogs: Vec<WorklogItem>| -> Vec<i64> {
+            worklogs.iter().map(|w| w.tempo_worklog_id).collect()
+        };
+
+        let full_query =
+            Matcher::Regex("^from=2025-04-01&to=2025-04-30&offset=0&limit=1000$".to_string());
+        let before = server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(full_query.clone())
+            .with_body(format!(
+                r#"{{"results": [{}, {}]}}"#,
+                worklog_json(1, 3600),
+                worklog_json(2, 1800)
+            ))
+            .expect(1)
+            .create_async()
+            .await;
+        let worklogs = mirrored_worklogs(&api, "2025-04-01", "2025-04-30", &args).await;
+        assert_eq!(ids(worklogs.unwrap()), vec![1, 2]);
+        before.assert_async().await;
+        before.remove_async().await;
+
+        // Worklog 2 is deleted in Tempo. Once the full fetch is old enough, the month is
+        // fetched in full again, which drops it
+        let after = server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(full_query)
+            .with_body(format!(r#"{{"results": [{}]}}"#, worklog_json(1, 3600)))
+            .expect(1)
+            .create_async()
+            .await;
+        api.storage
+            .store_mirror_fetched_at("2025-04", "2025-04-01T00:00:00Z");
+
+        let worklogs = mirrored_worklogs(&api, "2025-04-01", "2025-04-30", &args).await;
+        assert_eq!(ids(worklogs.unwrap()), vec![1]);
+        after.assert_async().await;
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    #[tokio::test]
+    async fn test_mirrored_worklogs_offline() {
+        let test_db_path = "test_mirrored_worklogs_offline";
+        let server = mockito::Server::new_async().await;
+        let api = create_test_api(test_db_path, &server.url());
+        let worklog = |id: i64, issue_id: i64| -> WorklogItem {
+            serde_json::from_str(&worklog_json(id, 3600).replace("10001", &issue_id.to_string()))
+                .unwrap()
+        };
+        api.storage.replace_mirrored_worklogs(
+            "2025-04-01",
+            "2025-04-30",
+            &[worklog(1, 10001), worklog(2, 10002)],
+        );
+        api.storage
+            .store_mirror_refreshed_at("2025-04", "2025-04-08T00:00:00Z");
+
+        // Jira isn't asked for the issue that isn't cached
+        let offline = MirrorArgs {
+            refresh: false,
+            offline: true,
+        };
+        let worklogs = mirrored_worklogs(&api, "2025-04-01", "2025-04-30", &offline)
+            .await
+            .unwrap();
+        let keys: Vec<Option<String>> = worklogs
+            .iter()
+            .map(|w| w.jira_issue.as_ref().map(|issue| issue.key.clone()))
+            .collect();
+        assert_eq!(keys, vec![Some("ABC-1".to_string()), None]);
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    #[test]
+    fn test_months_between() {
+        assert_eq!(
+            months_between("2024-11-15", "2025-02-01"),
+            vec!["2024-11", "2024-12", "2025-01", "2025-02"]
+        );
+        assert_eq!(months_between("2025-04-07", "2025-04-07"), vec!["2025-04"]);
+        assert!(months_between("2025-04-07", "2025-03-31").is_empty());
+        assert_eq!(
+            month_bounds("2024-02"),
+            ("2024-02-01".to_string(), "2024-02-29".to_string())
+        );
+    }
+}
@@ -4,10 +4,41 @@ mod log;
 mod delete;
 mod clean_db;
 mod list_range;
+mod create;
+mod alias;
+mod suggest;
+mod import_calendar;
+mod import;
+mod lint;
+mod edit;
+mod attributes;
+mod accounts;
+mod approvals;
+mod team;
+mod issue_log;
+mod search;
+mod journal;
+mod sync;
+mod mirror;
 
 pub use setup::setup;
 pub use list::list;
 pub use list_range::list_range;
-pub use log::log_time;
+pub use log::{log_time, RemainingArgs};
 pub use delete::delete_log;
 pub use clean_db::clean_jira_issues;
+pub use alias::{add_alias, list_aliases, remove_alias};
+pub use suggest::suggest;
+pub use import_calendar::import_calendar;
+pub use import::import;
+pub use create::{WorklogArgs, CreateOptions};
+pub use lint::lint;
+pub use edit::edit_worklog;
+pub use attributes::list_work_attributes;
+pub use approvals::{submit, approval_status};
+pub use team::{list_teams, team_week, review_timesheet};
+pub use issue_log::{issue_log, project_log};
+pub use search::search;
+pub use journal::{history, undo};
+pub use sync::sync;
+pub use mirror::MirrorArgs;
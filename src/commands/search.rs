@@ -0,0 +1,229 @@
+use crate::api::{ApiClient, ApiTrait};
+use crate::commands::list::truncate_string;
+use crate::models::{Alias, JiraIssue};
+
+use dialoguer::FuzzySelect;
+use regex::Regex;
+use spinners::{Spinner, Spinners};
+use tabled::{builder::Builder, settings::Style};
+
+const RECENT_ISSUES: usize = 10;
+
+// A query starting with a field and an operator, e.g. `project = ABC` or `status in (...)`,
+// or with an ORDER BY clause
+const JQL_PATTERN: &str = r#"(?i)^\s*(order\s+by\s|(\w[\w.]*|"[^"]+"|cf\[\d+\])\s*(!=|!~|<=|>=|=|~|<|>|(not\s+)?in\s*\(|is\s+(not\s+)?(empty|null)\b|was\s))"#;
+
+// Issue or alias offered by the picker
+#[derive(Debug, PartialEq)]
+struct Candidate {
+    value: String,
+    label: String,
+}
+
+pub async fn search(api: &ApiClient, query: &str, jql: bool, limit: u32) {
+    let mut spinner = Spinner::new(Spinners::Dots, "Searching issues...".to_string());
+    let query = if jql {
+        query.to_string()
+    } else {
+        to_jql(query)
+    };
+
+    match api.search_jira_issues(&query, limit).await {
+        Ok(issues) if issues.is_empty() => {
+            spinner.stop_with_message("\nNo issues found".to_string())
+        }
+        Ok(issues) => {
+            let mut builder = Builder::default();
+            builder.push_record(vec!["Key", "Summary", "Issue URL"]);
+
+            for issue in &issues {
+                builder.push_record(vec![
+                    issue.key.clone(),
+                    truncate_string(issue_summary(issue), 80),
+                    format!("{}/browse/{}", api.config.url, issue.key),
+                ]);
+            }
+
+            let mut table = builder.build();
+            table.with(Style::modern());
+            spinner.stop_with_message(format!("\n{}", table));
+        }
+        Err(e) => spinner.stop_with_message(format!("\nError. {}", e)),
+    }
+}
+
+// Let the user pick an issue from the ones in progress, the recent ones and the aliases.
+// Returns the issue key or alias name
+pub async fn pick_issue(api: &ApiClient) -> Result<String, String> {
+    let picker = &api.settings.picker;
+    let assigned = api
+        .search_jira_issues(&picker.jql, picker.max_results)
+        .await
+        .unwrap_or_default();
+    let recent = api.storage.get_recent_issues(RECENT_ISSUES);
+    let aliases = api.storage.list_aliases();
+
+    let candidates = build_candidates(&assigned, &recent, &aliases, |key| {
+        api.storage
+            .get_jira_issue(key)
+            .and_then(|issue| issue.fields)
+            .and_then(|fields| fields.summary)
+    });
+
+    if candidates.is_empty() {
+        return Err("No issue key given and no issues to pick from".to_string());
+    }
+
+    let labels: Vec<&str> = candidates.iter().map(|c| c.label.as_str()).collect();
+    let selection = FuzzySelect::new()
+        .with_prompt("Issue")
+        .items(&labels)
+        .default(0)
+        .interact_opt()
+        .map_err(|e| format!("Failed to read the selection: {}", e))?
+        .ok_or("No issue selected")?;
+
+    Ok(candidates[selection].value.clone())
+}
+
+// Issues in progress come first, then the recent ones and the aliases. Issues are listed once
+fn build_candidates(
+    assigned: &[JiraIssue],
+    recent: &[String],
+    aliases: &[Alias],
+    cached_summary: impl Fn(&str) -> Option<String>,
+) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = assigned
+        .iter()
+        .map(|issue| Candidate {
+            value: issue.key.clone(),
+            label: format!("{}  {}", issue.key, issue_summary(issue)),
+        })
+        .collect();
+
+    for issue_key in recent {
+        if candidates.iter().any(|c| &c.value == issue_key) {
+            continue;
+        }
+
+        candidates.push(Candidate {
+            value: issue_key.clone(),
+            label: match cached_summary(issue_key) {
+                Some(summary) => format!("{}  {} (recent)", issue_key, summary),
+                None => format!("{} (recent)", issue_key),
+            },
+        });
+    }
+
+    candidates.extend(aliases.iter().map(|alias| Candidate {
+        value: alias.name.clone(),
+        label: format!("{} -> {} (alias)", alias.name, alias.issue_key),
+    }));
+
+    candidates
+}
+
+// Plain text is searched in the issue text, a query that starts like JQL is used as is
+fn to_jql(query: &str) -> String {
+    if Regex::new(JQL_PATTERN).unwrap().is_match(query) {
+        query.to_string()
+    } else {
+        format!(
+            "text ~ \"{}\" ORDER BY updated DESC",
+            query.replace('\\', "\\\\").replace('"', "\\\"")
+        )
+    }
+}
+
+fn issue_summary(issue: &JiraIssue) -> &str {
+    issue
+        .fields
+        .as_ref()
+        .and_then(|fields| fields.summary.as_deref())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{JiraIssueFields, JiraProject};
+
+    #[test]
+    fn test_to_jql() {
+        assert_eq!(
+            to_jql("login page"),
+            "text ~ \"login page\" ORDER BY updated DESC"
+        );
+        assert_eq!(
+            to_jql("say \"hi\""),
+            "text ~ \"say \\\"hi\\\"\" ORDER BY updated DESC"
+        );
+        assert_eq!(to_jql("project = ABC"), "project = ABC");
+        assert_eq!(
+            to_jql("assignee IS EMPTY ORDER BY created"),
+            "assignee IS EMPTY ORDER BY created"
+        );
+        assert_eq!(
+            to_jql("status in (\"To Do\", Done)"),
+            "status in (\"To Do\", Done)"
+        );
+        assert_eq!(to_jql("ORDER BY created"), "ORDER BY created");
+
+        // Plain text that happens to contain JQL keywords
+        assert_eq!(
+            to_jql("bug in login"),
+            "text ~ \"bug in login\" ORDER BY updated DESC"
+        );
+        assert_eq!(
+            to_jql("page is blank"),
+            "text ~ \"page is blank\" ORDER BY updated DESC"
+        );
+        assert_eq!(
+            to_jql("crash when total > 100 in reports"),
+            "text ~ \"crash when total > 100 in reports\" ORDER BY updated DESC"
+        );
+    }
+
+    #[test]
+    fn test_build_candidates() {
+        let assigned = vec![JiraIssue {
+            id: "1".to_string(),
+            key: "ABC-1".to_string(),
+            fields: Some(JiraIssueFields {
+                project: JiraProject {
+                    id: "10".to_string(),
+                    key: "ABC".to_string(),
+                },
+                summary: Some("Login page".to_string()),
+            }),
+        }];
+        let recent = vec![
+            "ABC-1".to_string(),
+            "ABC-2".to_string(),
+            "ABC-3".to_string(),
+        ];
+        let aliases = vec![Alias {
+            name: "standup".to_string(),
+            issue_key: "INT-4".to_string(),
+            description: None,
+            duration: None,
+            billable: None,
+        }];
+
+        let candidates = build_candidates(&assigned, &recent, &aliases, |key| {
+            (key == "ABC-2").then(|| "Signup page".to_string())
+        });
+        let labels: Vec<&str> = candidates.iter().map(|c| c.label.as_str()).collect();
+
+        assert_eq!(
+            labels,
+            vec![
+                "ABC-1  Login page",
+                "ABC-2  Signup page (recent)",
+                "ABC-3 (recent)",
+                "standup -> INT-4 (alias)"
+            ]
+        );
+        assert_eq!(candidates[3].value, "standup");
+    }
+}
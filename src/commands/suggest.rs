@@ -0,0 +1,214 @@
+use crate::api::ApiClient;
+use crate::commands::create::{review_and_create_worklogs, CreateOptions};
+use crate::git::{self, Commit};
+use crate::models::NewWorklog;
+use crate::utils::parse_duration_from_string;
+
+use chrono::{Local, TimeZone};
+use spinners::{Spinner, Spinners};
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn suggest(
+    api: &ApiClient,
+    date_from: &str,
+    date_to: &str,
+    repos: &[PathBuf],
+    author: Option<String>,
+    session_cap: &str,
+    yes: bool,
+    options: &CreateOptions,
+) {
+    let session_cap = match parse_duration_from_string(session_cap, &api.settings.durations) {
+        Ok(session_cap) => session_cap as i64,
+        Err(e) => {
+            eprintln!("Error. {}", e);
+            return;
+        }
+    };
+    let author = author.unwrap_or_else(|| api.config.jira_email.clone());
+
+    let mut spinner = Spinner::new(Spinners::Dots, "Reading git history...".to_string());
+
+    let commits = match collect_commits(repos, &author, date_from, date_to) {
+        Ok(commits) => commits,
+        Err(e) => {
+            spinner.stop_with_message(format!("\nError. {}", e));
+            return;
+        }
+    };
+
+    let candidates: Vec<String> = commits.iter().flat_map(commit_issue_keys).collect();
+    let known_issues = api.find_existing_issues(&candidates).await;
+    let worklogs = estimate_worklogs(&commits, session_cap, &known_issues);
+
+    spinner.stop_with_message(format!(
+        "\nFound {} commit(s) by {} from {} to {}",
+        commits.len(),
+        author,
+        date_from,
+        date_to
+    ));
+
+    if worklogs.is_empty() {
+        println!("No commits referencing Jira issues, nothing to suggest");
+        return;
+    }
+
+    review_and_create_worklogs(api, worklogs, yes, options).await;
+}
+
+// Collect commits from all repositories, oldest first. A commit pushed to several
+// of the repositories is only counted once
+fn collect_commits(
+    repos: &[PathBuf],
+    author: &str,
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<Commit>, String> {
+    let mut commits: Vec<Commit> = Vec::new();
+
+    for repo in repos {
+        for commit in git::commits_by_author(repo, author, date_from, date_to)? {
+            if !commits.iter().any(|c| c.hash == commit.hash) {
+                commits.push(commit);
+            }
+        }
+    }
+
+    commits.sort_by_key(|commit| commit.timestamp);
+
+    Ok(commits)
+}
+
+// Issue keys mentioned in the commit, the subject goes first
+fn commit_issue_keys(commit: &Commit) -> Vec<String> {
+    let mut keys = git::find_issue_keys(&commit.subject);
+    keys.extend(git::find_issue_keys(&commit.body));
+    keys
+}
+
+// Estimate time spent per issue per day. Each commit is credited with the time passed since
+// the previous commit that day, but no more than the session cap. The first commit of a day
+// gets the whole cap. Commits without a known issue key aren't credited, but still count
+// as the end of the previous piece of work
+fn estimate_worklogs(
+    commits: &[Commit],
+    session_cap: i64,
+    known_issues: &HashSet<String>,
+) -> Vec<NewWorklog> {
+    let mut estimates: BTreeMap<(String, String), (i64, Vec<String>)> = BTreeMap::new();
+    let mut previous: Option<(String, i64)> = None;
+
+    for commit in commits {
+        let date = Local
+            .timestamp_opt(commit.timestamp, 0)
+            .unwrap()
+            .format("%Y-%m-%d")
+            .to_string();
+
+        let credited = match &previous {
+            Some((previous_date, previous_timestamp)) if *previous_date == date => {
+                (commit.timestamp - previous_timestamp).min(session_cap)
+            }
+            _ => session_cap,
+        };
+
+        previous = Some((date.clone(), commit.timestamp));
+
+        let Some(issue_key) = commit_issue_keys(commit)
+            .into_iter()
+            .find(|key| known_issues.contains(key))
+        else {
+            continue;
+        };
+
+        let (seconds, subjects) = estimates.entry((date, issue_key)).or_default();
+        *seconds += credited;
+
+        if !subjects.contains(&commit.subject) {
+            subjects.push(commit.subject.clone());
+        }
+    }
+
+    estimates
+        .into_iter()
+        .map(|((date, issue_key), (seconds, subjects))| NewWorklog {
+            issue_key,
+            time_spent_seconds: (seconds / 60 * 60) as i32,
+            description: subjects.join("; "),
+            start_date: date,
+            start_time: None,
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        })
+        .filter(|worklog| worklog.time_spent_seconds > 0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(hash: &str, time: &str, subject: &str) -> Commit {
+        let timestamp = Local
+            .from_local_datetime(
+                &chrono::NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M").unwrap(),
+            )
+            .unwrap()
+            .timestamp();
+
+        Commit {
+            hash: hash.to_string(),
+            timestamp,
+            subject: subject.to_string(),
+            body: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_estimate_worklogs() {
+        let known_issues: HashSet<String> = ["ABC-1", "ABC-2"].map(String::from).into();
+        let commits = vec![
+            commit("1", "2025-04-07 09:00", "ABC-1 Start feature"),
+            commit("2", "2025-04-07 09:45", "ABC-1 Add tests"),
+            commit("3", "2025-04-07 10:15", "ABC-2 Fix bug"),
+            commit("4", "2025-04-07 15:00", "ABC-2 Fix another bug"),
+            commit("5", "2025-04-07 15:20", "Bump version"),
+            commit("6", "2025-04-07 15:30", "ABC-1 Polish, see UTF-8 issue"),
+            commit("7", "2025-04-08 11:00", "ABC-1 Review fixes"),
+        ];
+
+        let worklogs = estimate_worklogs(&commits, 7200, &known_issues);
+
+        assert_eq!(worklogs.len(), 3);
+
+        // 2h for the first commit of the day, 45m until the second one and 10m after the bump
+        assert_eq!(worklogs[0].issue_key, "ABC-1");
+        assert_eq!(worklogs[0].start_date, "2025-04-07");
+        assert_eq!(worklogs[0].time_spent_seconds, 10500);
+        assert_eq!(
+            worklogs[0].description,
+            "ABC-1 Start feature; ABC-1 Add tests; ABC-1 Polish, see UTF-8 issue"
+        );
+
+        // 30m after the previous commit and the 2h cap after the lunch break
+        assert_eq!(worklogs[1].issue_key, "ABC-2");
+        assert_eq!(worklogs[1].start_date, "2025-04-07");
+        assert_eq!(worklogs[1].time_spent_seconds, 9000);
+
+        assert_eq!(worklogs[2].issue_key, "ABC-1");
+        assert_eq!(worklogs[2].start_date, "2025-04-08");
+        assert_eq!(worklogs[2].time_spent_seconds, 7200);
+    }
+
+    #[test]
+    fn test_estimate_worklogs_without_known_issues() {
+        let commits = vec![commit("1", "2025-04-07 09:00", "ABC-1 Start feature")];
+
+        assert!(estimate_worklogs(&commits, 7200, &HashSet::new()).is_empty());
+        assert!(estimate_worklogs(&[], 7200, &HashSet::new()).is_empty());
+    }
+}
@@ -0,0 +1,598 @@
+use crate::api::{is_offline_error, ApiClient, ApiTrait};
+use crate::commands::create::{create_worklogs, CreateOptions, DuplicatePolicy, WorklogArgs};
+use crate::commands::delete::check_deletable;
+use crate::commands::edit::update_worklog;
+use crate::commands::journal::record_deleted;
+use crate::commands::log::resolve_worklogs;
+use crate::models::{
+    NewWorklog, PendingChange, PendingCreateOptions, PendingOperation, WorklogItem,
+};
+use crate::storage::Storage;
+use crate::utils::format_duration;
+
+use chrono::{DateTime, Local};
+use spinners::{Spinner, Spinners};
+use std::collections::HashMap;
+use tabled::{builder::Builder, settings::Style};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncStatus {
+    // Replayed and removed from the queue
+    Synced,
+    // Already in Tempo, removed from the queue without replaying it
+    Skipped,
+    // Changed in Tempo since it was queued, kept until synced with --force or discarded
+    Conflict,
+    // Kept in the queue to be retried
+    Failed,
+}
+
+#[derive(Debug)]
+pub struct SyncResult {
+    pub operation: PendingOperation,
+    pub status: SyncStatus,
+    pub message: String,
+}
+
+pub fn queue_change(storage: &Storage, change: PendingChange) -> PendingOperation {
+    storage.add_pending_operation(PendingOperation {
+        id: 0,
+        queued_at: Local::now().to_rfc3339(),
+        change,
+    })
+}
+
+// Queue worklogs that couldn't be created and describe it to the user. The `options` left
+// to resolve are resolved when syncing
+pub fn queue_worklogs(
+    storage: &Storage,
+    worklogs: &[NewWorklog],
+    options: Option<PendingCreateOptions>,
+    force: bool,
+) -> String {
+    queue_change(
+        storage,
+        PendingChange::Create {
+            worklogs: worklogs.to_vec(),
+            force,
+            options,
+        },
+    );
+
+    format!(
+        "Network unavailable, {} worklog(s) queued. Run 'tempie sync' to log them",
+        worklogs.len()
+    )
+}
+
+// Queued worklogs to create that fall within the dates
+pub fn pending_worklogs(storage: &Storage, date_from: &str, date_to: &str) -> Vec<NewWorklog> {
+    storage
+        .list_pending_operations()
+        .into_iter()
+        .flat_map(|operation| match operation.change {
+            PendingChange::Create { worklogs, .. } => worklogs,
+            _ => Vec::new(),
+        })
+        .filter(|worklog| {
+            worklog.start_date.as_str() >= date_from && worklog.start_date.as_str() <= date_to
+        })
+        .collect()
+}
+
+// Labels of the existing worklogs with queued changes, e.g "pending edit"
+pub fn pending_labels(storage: &Storage) -> HashMap<String, &'static str> {
+    storage
+        .list_pending_operations()
+        .into_iter()
+        .filter_map(|operation| match operation.change {
+            PendingChange::Edit { worklog_id, .. } => Some((worklog_id, "pending edit")),
+            PendingChange::Delete { worklog_id } => Some((worklog_id, "pending delete")),
+            PendingChange::Create { .. } => None,
+        })
+        .collect()
+}
+
+pub async fn sync(api: &ApiClient, force: bool, discard: &[u64]) {
+    for id in discard {
+        if api.storage.remove_pending_operation(*id) {
+            println!("Discarded change #{}", id);
+        } else {
+            println!("Error. No queued change #{}", id);
+        }
+    }
+
+    if api.storage.list_pending_operations().is_empty() {
+        println!("Nothing to sync");
+        return;
+    }
+
+    let mut spinner = Spinner::new(Spinners::Dots, "Syncing...".to_string());
+    let results = sync_pending(api, force).await;
+    let left = api.storage.list_pending_operations().len();
+
+    let mut output = format!("\n{}", build_sync_table(&results));
+    if left > 0 {
+        output.push_str(&format!(
+            "\n{} change(s) left in the queue. Run 'tempie sync --force' to override the conflicts, \
+             or 'tempie sync --discard ID' to drop a change",
+            left
+        ));
+    }
+
+    spinner.stop_with_message(output);
+}
+
+// Replay the queued changes in order. Stops at the first one that fails for lack of network
+pub async fn sync_pending(api: &ApiClient, force: bool) -> Vec<SyncResult> {
+    let mut results = Vec::new();
+
+    for operation in api.storage.list_pending_operations() {
+        let (status, message) = replay(api, &operation, force).await;
+
+        if matches!(status, SyncStatus::Synced | SyncStatus::Skipped) {
+            api.storage.remove_pending_operation(operation.id);
+        }
+
+        let offline = status == SyncStatus::Failed && is_offline_error(&message);
+        results.push(SyncResult {
+            operation,
+            status,
+            message,
+        });
+
+        if offline {
+            break;
+        }
+    }
+
+    results
+}
+
+async fn replay(
+    api: &ApiClient,
+    operation: &PendingOperation,
+    force: bool,
+) -> (SyncStatus, String) {
+    match &operation.change {
+        PendingChange::Create {
+            worklogs,
+            force: queued_force,
+            options: pending_options,
+        } => {
+            let resolved = match pending_options {
+                Some(pending_options) => {
+                    resolve_worklogs(api, worklogs.clone(), pending_options).await
+                }
+                None => Ok(worklogs.clone()),
+            };
+            let worklogs = match resolved {
+                Ok(worklogs) => worklogs,
+                Err(e) => return (SyncStatus::Failed, e),
+            };
+
+            // Worklogs already in Tempo, e.g created before the network went down, are skipped.
+            // `force` only overrides the validation
+            let options = CreateOptions {
+                on_duplicate: DuplicatePolicy::Skip,
+                force: force || *queued_force,
+                no_round: true,
+            };
+
+            match create_worklogs(api, &worklogs, &options).await {
+                Ok(report) if report.created.is_empty() => {
+                    (SyncStatus::Skipped, report.warnings.join("\n"))
+                }
+                // Skipped duplicates of a batch are only reported as warnings
+                Ok(report) => {
+                    let mut lines = vec![format!(
+                        "Created worklog(s) {}",
+                        created_ids(&report.created)
+                    )];
+                    lines.extend(report.warnings);
+                    (SyncStatus::Synced, lines.join("\n"))
+                }
+                Err(e) => (SyncStatus::Failed, e.message),
+            }
+        }
+        PendingChange::Edit {
+            worklog_id,
+            changes,
+            billable,
+            non_billable,
+            attributes,
+            account,
+            force: queued_force,
+        } => {
+            if let Err(conflict) =
+                check_conflict(api, worklog_id, &operation.queued_at, force).await
+            {
+                return conflict;
+            }
+
+            let worklog_args = WorklogArgs {
+                billable: billable.clone(),
+                non_billable: *non_billable,
+                attributes: attributes.clone(),
+                account: account.clone(),
+            };
+
+            match update_worklog(
+                api,
+                worklog_id,
+                changes,
+                &worklog_args,
+                force || *queued_force,
+            )
+            .await
+            {
+                Ok(_) => (
+                    SyncStatus::Synced,
+                    format!("Updated worklog {}", worklog_id),
+                ),
+                Err(e) => (SyncStatus::Failed, e),
+            }
+        }
+        PendingChange::Delete { worklog_id } => {
+            let worklog = match check_conflict(api, worklog_id, &operation.queued_at, force).await {
+                Ok(worklog) => worklog,
+                Err(conflict) => return conflict,
+            };
+            if !force {
+                if let Err(e) = check_deletable(api, &worklog).await {
+                    return (SyncStatus::Conflict, e);
+                }
+            }
+
+            match api.delete_worklogs(&vec![worklog_id.clone()]).await {
+                Ok(_) => {
+                    record_deleted(
+                        &api.storage,
+                        worklog_id,
+                        NewWorklog::from_worklog_item(&worklog),
+                    );
+                    (
+                        SyncStatus::Synced,
+                        format!("Deleted worklog {}", worklog_id),
+                    )
+                }
+                Err(e) => (SyncStatus::Failed, e),
+            }
+        }
+    }
+}
+
+// Fetch the worklog to change. It's a conflict if it's gone or was updated after the change
+// was queued, unless `force` is set
+async fn check_conflict(
+    api: &ApiClient,
+    worklog_id: &str,
+    queued_at: &str,
+    force: bool,
+) -> Result<WorklogItem, (SyncStatus, String)> {
+    let worklog = match api.get_worklog(worklog_id).await {
+        Ok(worklog) => worklog,
+        Err(e) if is_offline_error(&e) => return Err((SyncStatus::Failed, e)),
+        Err(e) => return Err((SyncStatus::Conflict, e)),
+    };
+
+    if !force && updated_after(&worklog.updated_at, queued_at) {
+        return Err((
+            SyncStatus::Conflict,
+            format!(
+                "Worklog {} was changed in Tempo at {}, after this change was queued",
+                worklog_id, worklog.updated_at
+            ),
+        ));
+    }
+
+    Ok(worklog)
+}
+
+fn updated_after(updated_at: &str, queued_at: &str) -> bool {
+    match (
+        DateTime::parse_from_rfc3339(updated_at),
+        DateTime::parse_from_rfc3339(queued_at),
+    ) {
+        (Ok(updated_at), Ok(queued_at)) => updated_at > queued_at,
+        _ => false,
+    }
+}
+
+fn created_ids(created: &[(NewWorklog, WorklogItem)]) -> String {
+    created
+        .iter()
+        .map(|(_, item)| item.tempo_worklog_id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// e.g "log ABC-1 1h on 2025-04-07" or "edit worklog 12345"
+pub fn describe(change: &PendingChange) -> String {
+    match change {
+        PendingChange::Create { worklogs, .. } => {
+            let worklogs: Vec<String> = worklogs
+                .iter()
+                .map(|w| {
+                    format!(
+                        "{} {} on {}",
+                        w.issue_key,
+                        format_duration(w.time_spent_seconds),
+                        w.start_date
+                    )
+                })
+                .collect();
+            format!("log {}", worklogs.join(", "))
+        }
+        PendingChange::Edit { worklog_id, .. } => format!("edit worklog {}", worklog_id),
+        PendingChange::Delete { worklog_id } => format!("delete worklog {}", worklog_id),
+    }
+}
+
+fn build_sync_table(results: &[SyncResult]) -> tabled::Table {
+    let mut builder = Builder::default();
+    builder.push_record(vec!["#", "Change", "Result", "Details"]);
+
+    for result in results {
+        builder.push_record(vec![
+            result.operation.id.to_string(),
+            describe(&result.operation.change),
+            match result.status {
+                SyncStatus::Synced => "synced",
+                SyncStatus::Skipped => "skipped",
+                SyncStatus::Conflict => "conflict",
+                SyncStatus::Failed => "failed",
+            }
+            .to_string(),
+            result.message.clone(),
+        ]);
+    }
+
+    let mut table = builder.build();
+    table.with(Style::modern());
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::create_test_api;
+    use crate::models::{JiraIssue, JiraIssueFields, JiraProject};
+    use mockito::Matcher;
+
+    // Nothing listens on this port, so requests fail like without network
+    const UNREACHABLE_URL: &str = "http://127.0.0.1:1";
+
+    fn worklog() -> NewWorklog {
+        NewWorklog {
+            issue_key: "ABC-1".to_string(),
+            time_spent_seconds: 3600,
+            description: "Review".to_string(),
+            start_date: "2025-04-07".to_string(),
+            start_time: None,
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queue_and_sync() {
+        let test_db_path = "test_sync_queue";
+        let mut server = mockito::Server::new_async().await;
+        // Offline: the worklog and the deletion are queued
+        let api = create_test_api(test_db_path, &server.url()).with_tempo_url(UNREACHABLE_URL);
+        let error = create_worklogs(&api, &[worklog()], &CreateOptions::default())
+            .await
+            .unwrap_err();
+        assert!(is_offline_error(&error.message));
+
+        queue_worklogs(&api.storage, &[worklog()], None, false);
+        queue_change(
+            &api.storage,
+            PendingChange::Delete {
+                worklog_id: "77".to_string(),
+            },
+        );
+        assert_eq!(
+            pending_worklogs(&api.storage, "2025-04-07", "2025-04-07").len(),
+            1
+        );
+        assert_eq!(pending_labels(&api.storage)["77"], "pending delete");
+
+        // Still offline: the sync stops at the first change and keeps the queue
+        let results = sync_pending(&api, false).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, SyncStatus::Failed);
+        assert_eq!(api.storage.list_pending_operations().len(), 2);
+
+        // Online again. The worklog to delete was changed in Tempo after it was queued
+        let api = api.with_tempo_url(&server.url());
+        server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(Matcher::Any)
+            .with_body(r#"{"results": []}"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/work-attributes")
+            .with_body(r#"{"results": []}"#)
+            .create_async()
+            .await;
+        let created = server
+            .mock("POST", "/worklogs/")
+            .with_body(
+                r#"{"tempoWorklogId": 501, "timeSpentSeconds": 3600, "description": "Review",
+                    "startDate": "2025-04-07", "createdAt": "2025-04-07T10:00:00Z",
+                    "issue": {"id": 10001}}"#,
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/worklogs/77")
+            .with_body(
+                r#"{"tempoWorklogId": 77, "timeSpentSeconds": 1800, "description": "Standup",
+                    "startDate": "2025-04-07", "createdAt": "2025-04-07T10:00:00Z",
+                    "updatedAt": "2999-01-01T00:00:00Z", "issue": {"id": 10001}}"#,
+            )
+            .create_async()
+            .await;
+
+        let results = sync_pending(&api, false).await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].status, SyncStatus::Synced);
+        assert_eq!(results[0].message, "Created worklog(s) 501");
+        assert_eq!(results[1].status, SyncStatus::Conflict);
+        assert!(results[1].message.contains("changed in Tempo"));
+        created.assert_async().await;
+
+        // Forced, the conflicting deletion goes through
+        let deleted = server
+            .mock("DELETE", "/worklogs/77")
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let results = sync_pending(&api, true).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, SyncStatus::Synced);
+        assert!(api.storage.list_pending_operations().is_empty());
+        deleted.assert_async().await;
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    #[tokio::test]
+    async fn test_sync_resolves_queued_options() {
+        let test_db_path = "test_sync_queued_options";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_test_api(test_db_path, &server.url()).with_tempo_url(UNREACHABLE_URL);
+        api.storage.store_jira_issue(&JiraIssue {
+            id: "10001".to_string(),
+            key: "ABC-1".to_string(),
+            fields: Some(JiraIssueFields {
+                project: JiraProject {
+                    id: "100".to_string(),
+                    key: "ABC".to_string(),
+                },
+                summary: None,
+            }),
+        });
+        let options = PendingCreateOptions {
+            account: Some("DEV".to_string()),
+            ..PendingCreateOptions::default()
+        };
+
+        // Offline, the account can't be looked up, so the worklog is queued without it
+        let error = resolve_worklogs(&api, vec![worklog()], &options)
+            .await
+            .unwrap_err();
+        assert!(is_offline_error(&error));
+        queue_worklogs(&api.storage, &[worklog()], Some(options), false);
+
+        // Online again, the account is resolved and sent with the worklog
+        let api = api.with_tempo_url(&server.url());
+        server
+            .mock("GET", "/accounts")
+            .with_body(r#"{"results": [{"id": 1, "key": "DEV", "name": "Development", "status": "OPEN"}]}"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/account-links/project/100")
+            .with_body(r#"{"results": [{"account": {"id": 1}, "default": true}]}"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/work-attributes")
+            .with_body(r#"{"results": []}"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(Matcher::Any)
+            .with_body(r#"{"results": []}"#)
+            .create_async()
+            .await;
+        let created = server
+            .mock("POST", "/worklogs/")
+            .match_body(Matcher::Regex(r#""_Account_".*"DEV""#.to_string()))
+            .with_body(
+                r#"{"tempoWorklogId": 501, "timeSpentSeconds": 3600, "description": "Review",
+                    "startDate": "2025-04-07", "createdAt": "2025-04-07T10:00:00Z",
+                    "issue": {"id": 10001}}"#,
+            )
+            .create_async()
+            .await;
+
+        let results = sync_pending(&api, false).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, SyncStatus::Synced);
+        created.assert_async().await;
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    #[tokio::test]
+    async fn test_sync_partially_skipped_batch() {
+        let test_db_path = "test_sync_partially_skipped";
+        let mut server = mockito::Server::new_async().await;
+        let api = create_test_api(test_db_path, &server.url());
+        let standup = NewWorklog {
+            description: "Standup".to_string(),
+            ..worklog()
+        };
+        queue_worklogs(&api.storage, &[worklog(), standup], None, false);
+
+        // The review was created before the network went down, only the standup is posted
+        server
+            .mock("GET", "/worklogs/user/test123")
+            .match_query(Matcher::Any)
+            .with_body(
+                r#"{"results": [{"tempoWorklogId": 500, "timeSpentSeconds": 3600,
+                    "description": "Review", "startDate": "2025-04-07",
+                    "startTime": "09:00:00", "createdAt": "2025-04-07T10:00:00Z",
+                    "issue": {"id": 10001}}]}"#,
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/work-attributes")
+            .with_body(r#"{"results": []}"#)
+            .create_async()
+            .await;
+        let created = server
+            .mock("POST", "/worklogs/")
+            .match_body(Matcher::Regex("Standup".to_string()))
+            .with_body(
+                r#"{"tempoWorklogId": 501, "timeSpentSeconds": 3600, "description": "Standup",
+                    "startDate": "2025-04-07", "createdAt": "2025-04-07T10:00:00Z",
+                    "issue": {"id": 10001}}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let results = sync_pending(&api, false).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, SyncStatus::Synced);
+        assert!(results[0].message.starts_with("Created worklog(s) 501\n"));
+        assert!(results[0].message.contains("duplicates worklog 500"));
+        assert!(api.storage.list_pending_operations().is_empty());
+        created.assert_async().await;
+
+        drop(api);
+        let _ = std::fs::remove_dir_all(test_db_path);
+    }
+
+    #[test]
+    fn test_updated_after() {
+        let queued_at = "2025-04-07T12:00:00+02:00";
+
+        assert!(updated_after("2025-04-07T10:30:00Z", queued_at));
+        assert!(!updated_after("2025-04-07T09:30:00Z", queued_at));
+        assert!(!updated_after("", queued_at));
+    }
+}
@@ -0,0 +1,344 @@
+use crate::api::{ApiClient, ApiTrait, CONCURRENT_REQUESTS};
+use crate::commands::approvals::{find_period, user_name};
+use crate::commands::list::apply_common_formatting;
+use crate::models::{Period, Team, TimesheetReview};
+use crate::utils::format_duration;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use futures::{stream, StreamExt};
+use spinners::{Spinner, Spinners};
+use std::collections::HashMap;
+use tabled::{
+    builder::Builder,
+    settings::object::{Cell, Rows},
+    settings::{Alignment, Span},
+    Table,
+};
+
+// Time a team member logged and was required to log per day
+#[derive(Debug, Default)]
+struct MemberWeek {
+    name: String,
+    logged: HashMap<NaiveDate, i32>,
+    required: HashMap<NaiveDate, i32>,
+    status: Option<String>,
+    // Why the member's worklogs or schedule couldn't be retrieved
+    error: Option<String>,
+}
+
+pub async fn list_teams(api: &ApiClient) {
+    let mut spinner = Spinner::new(Spinners::Dots, "Retrieving teams...".to_string());
+
+    let teams = match api.list_teams().await {
+        Ok(teams) => teams,
+        Err(e) => {
+            spinner.stop_with_message(format!("\nError. {}", e));
+            return;
+        }
+    };
+
+    let mut rows: Vec<(Team, Vec<String>)> = stream::iter(teams)
+        .map(|team| async move {
+            let mut names = Vec::new();
+            if let Ok(members) = api.list_team_members(team.id).await {
+                for member in members {
+                    names.push(user_name(api, &member.member.account_id).await);
+                }
+            }
+            names.sort();
+            (team, names)
+        })
+        .buffer_unordered(CONCURRENT_REQUESTS)
+        .collect()
+        .await;
+    rows.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+
+    let mut builder = Builder::default();
+    builder.push_record(vec!["ID", "Name", "Members"]);
+    for (team, names) in rows {
+        builder.push_record(vec![team.id.to_string(), team.name, names.join("\n")]);
+    }
+
+    let mut table = builder.build();
+    table.with(tabled::settings::Style::modern());
+
+    spinner.stop_with_message(format!("\n{}", table));
+}
+
+pub async fn team_week(api: &ApiClient, team: &str, date: &str) {
+    let mut spinner = Spinner::new(Spinners::Dots, "Retrieving team worklogs...".to_string());
+
+    match fetch_team_week(api, team, date).await {
+        Ok((team, days, members)) => spinner.stop_with_message(format!(
+            "\n{}",
+            build_week_table(&team.name, &days, &members)
+        )),
+        Err(e) => spinner.stop_with_message(format!("\nError. {}", e)),
+    }
+}
+
+pub async fn review_timesheet(
+    api: &ApiClient,
+    account_id: &str,
+    date: &str,
+    review: TimesheetReview,
+    comment: Option<String>,
+) {
+    let mut spinner = Spinner::new(Spinners::Dots, "Reviewing timesheet...".to_string());
+
+    let result = match find_period(api, date).await {
+        Ok(period) => {
+            api.review_timesheet(account_id, &period, review, comment.as_deref())
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(approval) => spinner.stop_with_message(format!(
+            "\nTimesheet of {} for {} to {} is now {}",
+            user_name(api, account_id).await,
+            approval.period.from,
+            approval.period.to,
+            approval.status.label()
+        )),
+        Err(e) => spinner.stop_with_message(format!("\nError. {}", e)),
+    }
+}
+
+async fn fetch_team_week(
+    api: &ApiClient,
+    team: &str,
+    date: &str,
+) -> Result<(Team, Vec<NaiveDate>, Vec<MemberWeek>), String> {
+    let team = find_team(api.list_teams().await?, team)?;
+    let members = api.list_team_members(team.id).await?;
+
+    let days = week_days(NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap());
+    let date_from = days[0].format("%Y-%m-%d").to_string();
+    let date_to = days[6].format("%Y-%m-%d").to_string();
+    let period = find_period(api, &date_from).await.ok();
+
+    let mut members: Vec<MemberWeek> = stream::iter(members)
+        .map(|member| {
+            let (date_from, date_to, period) = (&date_from, &date_to, &period);
+            async move {
+                fetch_member_week(api, &member.member.account_id, date_from, date_to, period).await
+            }
+        })
+        .buffer_unordered(CONCURRENT_REQUESTS)
+        .collect()
+        .await;
+    members.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok((team, days, members))
+}
+
+async fn fetch_member_week(
+    api: &ApiClient,
+    account_id: &str,
+    date_from: &str,
+    date_to: &str,
+    period: &Option<Period>,
+) -> MemberWeek {
+    let mut week = MemberWeek {
+        name: user_name(api, account_id).await,
+        ..MemberWeek::default()
+    };
+
+    // One member failing doesn't hide the rest of the team
+    if let Err(e) = load_member_week(api, &mut week, account_id, date_from, date_to, period).await {
+        week.logged.clear();
+        week.required.clear();
+        week.error = Some(e);
+    }
+
+    week
+}
+
+async fn load_member_week(
+    api: &ApiClient,
+    week: &mut MemberWeek,
+    account_id: &str,
+    date_from: &str,
+    date_to: &str,
+    period: &Option<Period>,
+) -> Result<(), String> {
+    for worklog in api
+        .list_user_worklogs(account_id, date_from, date_to)
+        .await?
+    {
+        *week
+            .logged
+            .entry(parse_date(&worklog.start_date))
+            .or_default() += worklog.time_spent_seconds;
+    }
+
+    for day in api
+        .get_user_schedule(account_id, date_from, date_to)
+        .await?
+    {
+        week.required
+            .insert(parse_date(&day.date), day.required_seconds);
+    }
+
+    if let Some(period) = period {
+        week.status = api
+            .get_timesheet_approval(account_id, period)
+            .await
+            .ok()
+            .map(|approval| approval.status.label().to_string());
+    }
+
+    Ok(())
+}
+
+// Find a team by its ID or name, ignoring case
+fn find_team(teams: Vec<Team>, team: &str) -> Result<Team, String> {
+    teams
+        .into_iter()
+        .find(|t| t.id.to_string() == team || t.name.eq_ignore_ascii_case(team))
+        .ok_or_else(|| format!("Team '{}' not found", team))
+}
+
+// Monday to Sunday of the week the date falls into
+fn week_days(date: NaiveDate) -> Vec<NaiveDate> {
+    let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+
+    (0..7).map(|i| monday + Duration::days(i)).collect()
+}
+
+fn build_week_table(team: &str, days: &[NaiveDate], members: &[MemberWeek]) -> Table {
+    let mut builder = Builder::default();
+    let columns = days.len() as isize + 3;
+
+    builder.push_record(vec![format!(
+        "{}, {} to {}",
+        team,
+        days[0],
+        days[days.len() - 1]
+    )]);
+
+    let mut headers = vec!["Member".to_string()];
+    headers.extend(days.iter().map(|day| day.format("%a %d").to_string()));
+    headers.extend(["Total".to_string(), "Status".to_string()]);
+    builder.push_record(headers);
+
+    let (mut team_logged, mut team_required) = (0, 0);
+    let mut error_rows = Vec::new();
+
+    for member in members {
+        if let Some(error) = &member.error {
+            let mut row = vec![member.name.clone(), format!("Error. {}", error)];
+            row.resize(days.len() + 3, String::new());
+            error_rows.push(builder.count_records());
+            builder.push_record(row);
+            continue;
+        }
+
+        let mut row = vec![member.name.clone()];
+        let (mut logged, mut required) = (0, 0);
+
+        for day in days {
+            let day_logged = member.logged.get(day).copied().unwrap_or(0);
+            let day_required = member.required.get(day).copied().unwrap_or(0);
+            logged += day_logged;
+            required += day_required;
+
+            row.push(format_logged(day_logged, day_required));
+        }
+
+        row.push(format_logged(logged, required));
+        row.push(member.status.clone().unwrap_or_default());
+        builder.push_record(row);
+
+        team_logged += logged;
+        team_required += required;
+    }
+
+    builder.push_record(vec![format!(
+        "Total {}",
+        format_logged(team_logged, team_required)
+    )]);
+
+    let mut table = builder.build();
+    table.modify(Rows::first(), Span::column(columns));
+    table.modify(Rows::last(), Span::column(columns));
+    for row in error_rows {
+        table.modify(Cell::new(row, 1), Span::column(days.len() as isize + 1));
+    }
+    table.modify(Rows::first(), Alignment::center());
+    table.modify(Rows::last(), Alignment::right());
+    apply_common_formatting(&mut table);
+
+    table
+}
+
+// "6h/8h", or nothing on days off without any time logged
+fn format_logged(logged: i32, required: i32) -> String {
+    if logged == 0 && required == 0 {
+        String::new()
+    } else {
+        format!("{}/{}", format_duration(logged), format_duration(required))
+    }
+}
+
+fn parse_date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_week_days() {
+        let days = week_days(NaiveDate::from_ymd_opt(2025, 4, 10).unwrap());
+
+        assert_eq!(days.len(), 7);
+        assert_eq!(days[0], NaiveDate::from_ymd_opt(2025, 4, 7).unwrap());
+        assert_eq!(days[6], NaiveDate::from_ymd_opt(2025, 4, 13).unwrap());
+    }
+
+    #[test]
+    fn test_find_team() {
+        let teams = || {
+            vec![Team {
+                id: 4,
+                name: "Platform".to_string(),
+            }]
+        };
+
+        assert_eq!(find_team(teams(), "4").unwrap().name, "Platform");
+        assert_eq!(find_team(teams(), "platform").unwrap().id, 4);
+        assert!(find_team(teams(), "Mobile").is_err());
+    }
+
+    #[test]
+    fn test_build_week_table() {
+        let days = week_days(NaiveDate::from_ymd_opt(2025, 4, 7).unwrap());
+        let member = MemberWeek {
+            name: "Jane Doe".to_string(),
+            logged: HashMap::from([(days[0], 6 * 3600), (days[1], 8 * 3600)]),
+            required: days[..5].iter().map(|day| (*day, 8 * 3600)).collect(),
+            status: Some("waiting".to_string()),
+            error: None,
+        };
+        let failed = MemberWeek {
+            name: "John Roe".to_string(),
+            error: Some("Failed to get the schedule".to_string()),
+            ..MemberWeek::default()
+        };
+
+        let table = build_week_table("Platform", &days, &[member, failed]).to_string();
+
+        assert!(table.contains("Platform, 2025-04-07 to 2025-04-13"));
+        assert!(table.contains("Mon 07"));
+        assert!(table.contains("6h/8h"));
+        assert!(table.contains("0h/8h"));
+        assert!(table.contains("14h/40h"));
+        assert!(table.contains("waiting"));
+        assert!(table.contains("Error. Failed to get the schedule"));
+        assert!(table.contains("Total 14h/40h"));
+    }
+}
@@ -0,0 +1,223 @@
+use regex::Regex;
+use std::path::Path;
+use std::process::Command;
+
+const ISSUE_KEY_PATTERN: &str = r"\b[A-Z][A-Z0-9_]+-\d+";
+const FIELD_SEPARATOR: char = '\x1f';
+const RECORD_SEPARATOR: char = '\x1e';
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commit {
+    pub hash: String,
+    pub timestamp: i64,
+    pub subject: String,
+    pub body: String,
+}
+
+// Run a git command in the given repository and return its output
+fn run_git(repo: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// Get the name of the currently checked out branch
+pub fn current_branch(repo: &Path) -> Option<String> {
+    run_git(repo, &["rev-parse", "--abbrev-ref", "HEAD"])
+        .ok()
+        .map(|branch| branch.trim().to_string())
+        .filter(|branch| !branch.is_empty() && branch != "HEAD")
+}
+
+// Get the subjects of the latest commits, newest first
+pub fn recent_commit_messages(repo: &Path, count: usize) -> Vec<String> {
+    run_git(repo, &["log", &format!("-{}", count), "--format=%s"])
+        .map(|output| output.lines().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+// Get commits from all branches made by the author between two dates (format: YYYY-MM-DD)
+pub fn commits_by_author(
+    repo: &Path,
+    author: &str,
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<Commit>, String> {
+    let output = run_git(
+        repo,
+        &[
+            "log",
+            "--all",
+            "--no-merges",
+            &format!("--author={}", author),
+            &format!("--since={} 00:00:00", date_from),
+            &format!("--until={} 23:59:59", date_to),
+            "--format=%H%x1f%at%x1f%s%x1f%b%x1e",
+        ],
+    )
+    .map_err(|e| format!("Failed to read commits from {}: {}", repo.display(), e))?;
+
+    Ok(output
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let fields: Vec<&str> = record.trim_start().splitn(4, FIELD_SEPARATOR).collect();
+
+            match fields.as_slice() {
+                [hash, timestamp, subject, body] => Some(Commit {
+                    hash: hash.to_string(),
+                    timestamp: timestamp.parse().ok()?,
+                    subject: subject.to_string(),
+                    body: body.trim().to_string(),
+                }),
+                _ => None,
+            }
+        })
+        .collect())
+}
+
+// Find Jira issue keys in the text, e.g "feature/ABC-123-some-thing" -> ["ABC-123"].
+// Lowercase keys are accepted as well, because branch names are often lowercase
+pub fn find_issue_keys(text: &str) -> Vec<String> {
+    let regex = Regex::new(ISSUE_KEY_PATTERN).unwrap();
+    let mut keys: Vec<String> = Vec::new();
+
+    for key in regex.find_iter(&text.to_uppercase()) {
+        if !keys.iter().any(|k| k == key.as_str()) {
+            keys.push(key.as_str().to_string());
+        }
+    }
+
+    keys
+}
+
+// Collect issue keys from the current branch name and, optionally, the recent commits
+pub fn find_issue_key_candidates(repo: &Path, commits: usize) -> Vec<String> {
+    let mut candidates = current_branch(repo)
+        .map(|branch| find_issue_keys(&branch))
+        .unwrap_or_default();
+
+    for message in recent_commit_messages(repo, commits) {
+        for key in find_issue_keys(&message) {
+            if !candidates.contains(&key) {
+                candidates.push(key);
+            }
+        }
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Create a git repository with the branch and commits for testing
+    fn init_test_repo(path: &str, branch: &str, messages: &[&str]) {
+        let _ = std::fs::remove_dir_all(path);
+        std::fs::create_dir_all(path).unwrap();
+
+        let repo = Path::new(path);
+        run_git(repo, &["init", "-q", "-b", branch]).unwrap();
+
+        for message in messages {
+            run_git(
+                repo,
+                &[
+                    "-c",
+                    "user.name=Test",
+                    "-c",
+                    "user.email=test@example.com",
+                    "commit",
+                    "-q",
+                    "--allow-empty",
+                    "-m",
+                    message,
+                ],
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_find_issue_keys() {
+        assert_eq!(
+            find_issue_keys("feature/ABC-123-some-thing"),
+            vec!["ABC-123"]
+        );
+        assert_eq!(find_issue_keys("bugfix/abc-42_fix"), vec!["ABC-42"]);
+        assert_eq!(
+            find_issue_keys("ABC-1 and XY2-20, again ABC-1"),
+            vec!["ABC-1", "XY2-20"]
+        );
+
+        assert!(find_issue_keys("main").is_empty());
+        assert!(find_issue_keys("feature/no-key-here").is_empty());
+        assert!(find_issue_keys("ABC-").is_empty());
+    }
+
+    #[test]
+    fn test_find_issue_key_candidates() {
+        let test_repo_path = "test_find_issue_key_candidates";
+        init_test_repo(
+            test_repo_path,
+            "feature/ABC-123-some-thing",
+            &["ABC-7 first", "Second without key", "XYZ-9 and ABC-123"],
+        );
+
+        let repo = Path::new(test_repo_path);
+
+        assert_eq!(
+            current_branch(repo),
+            Some("feature/ABC-123-some-thing".to_string())
+        );
+        assert_eq!(find_issue_key_candidates(repo, 0), vec!["ABC-123"]);
+        assert_eq!(
+            find_issue_key_candidates(repo, 10),
+            vec!["ABC-123", "XYZ-9", "ABC-7"]
+        );
+
+        let _ = std::fs::remove_dir_all(test_repo_path);
+    }
+
+    #[test]
+    fn test_commits_by_author() {
+        let test_repo_path = "test_commits_by_author";
+        init_test_repo(
+            test_repo_path,
+            "main",
+            &["ABC-1 first", "ABC-2 second\n\nLonger description"],
+        );
+
+        let repo = Path::new(test_repo_path);
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+        let commits = commits_by_author(repo, "test@example.com", &today, &today).unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].subject, "ABC-2 second");
+        assert_eq!(commits[0].body, "Longer description");
+        assert_eq!(commits[1].subject, "ABC-1 first");
+        assert_eq!(commits[1].body, "");
+        assert!(commits[0].timestamp > 0);
+
+        let commits = commits_by_author(repo, "other@example.com", &today, &today).unwrap();
+        assert!(commits.is_empty());
+
+        let commits = commits_by_author(repo, "test@example.com", "2020-01-01", "2020-01-31");
+        assert!(commits.unwrap().is_empty());
+
+        assert!(
+            commits_by_author(Path::new("missing"), "test@example.com", &today, &today).is_err()
+        );
+
+        let _ = std::fs::remove_dir_all(test_repo_path);
+    }
+}
@@ -0,0 +1,774 @@
+use super::load_file;
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use regex::Regex;
+use serde::Deserialize;
+use std::path::Path;
+
+const DEFAULT_RULES_FILE: &str = "calendar.toml";
+
+// Upper bound of generated occurrences per recurring event, protects from endless rules
+const MAX_OCCURRENCES: usize = 5000;
+
+// Content line split into the name, parameters and value
+type Property = (String, Vec<(String, String)>, String);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub summary: String,
+    pub organizer: Option<String>,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub all_day: bool,
+    pub declined: bool,
+    pub cancelled: bool,
+    // Why the event can't be imported as it is, e.g it's in another timezone
+    pub unsupported: Option<&'static str>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct CalendarRules {
+    pub default: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<CalendarRule>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarRule {
+    pub summary: Option<String>,
+    pub organizer: Option<String>,
+    pub issue: String,
+}
+
+// A VEVENT as it's written in the file, before recurrences are expanded
+#[derive(Debug, Default)]
+struct RawEvent {
+    uid: String,
+    summary: String,
+    organizer: Option<String>,
+    start: Option<NaiveDateTime>,
+    end: Option<NaiveDateTime>,
+    // TZID of the start, unless it's UTC
+    timezone: Option<String>,
+    duration: Option<Duration>,
+    all_day: bool,
+    declined: bool,
+    cancelled: bool,
+    rrule: Option<String>,
+    exdates: Vec<NaiveDateTime>,
+    recurrence_id: Option<NaiveDateTime>,
+}
+
+impl CalendarRules {
+    // Load the rules file given by the user or the default one, if it exists
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        load_file(path, DEFAULT_RULES_FILE, "calendar rules")
+    }
+
+    // Find the issue key or alias for the event. The first matching rule wins,
+    // events matching no rule go to the default issue
+    pub fn match_issue(&self, event: &CalendarEvent) -> Result<Option<String>, String> {
+        for rule in &self.rules {
+            let summary_matches = pattern_matches(&rule.summary, Some(&event.summary))?;
+            let organizer_matches = pattern_matches(&rule.organizer, event.organizer.as_deref())?;
+
+            if summary_matches && organizer_matches {
+                return Ok(Some(rule.issue.clone()));
+            }
+        }
+
+        Ok(self.default.clone())
+    }
+}
+
+// Missing pattern matches anything, a pattern never matches a missing value
+fn pattern_matches(pattern: &Option<String>, value: Option<&str>) -> Result<bool, String> {
+    let Some(pattern) = pattern else {
+        return Ok(true);
+    };
+
+    let regex = Regex::new(pattern).map_err(|e| format!("Invalid rule pattern: {}", e))?;
+
+    Ok(value.is_some_and(|value| regex.is_match(value)))
+}
+
+// Parse events from ICS content between the dates. Recurring events are expanded within them.
+// Events are marked as declined when the attendee with the email declined them
+pub fn parse_events(
+    content: &str,
+    email: &str,
+    from: NaiveDate,
+    until: NaiveDate,
+) -> Vec<CalendarEvent> {
+    let timezone = iana_time_zone::get_timezone().ok();
+
+    parse_events_in(content, email, from, until, timezone.as_deref())
+}
+
+// Times with a TZID are only taken as they are in the local timezone. There's no timezone
+// database to convert the others, so they're marked as unsupported
+fn parse_events_in(
+    content: &str,
+    email: &str,
+    from: NaiveDate,
+    until: NaiveDate,
+    local_timezone: Option<&str>,
+) -> Vec<CalendarEvent> {
+    let raw_events = parse_raw_events(content, email);
+    let mut events: Vec<CalendarEvent> = Vec::new();
+    let mut unexpanded: Vec<CalendarEvent> = Vec::new();
+    let unsupported = |raw: &RawEvent| {
+        raw.timezone
+            .as_deref()
+            .filter(|timezone| Some(*timezone) != local_timezone)
+            .map(|_| "in another timezone")
+    };
+
+    for raw in raw_events.iter().filter(|raw| raw.recurrence_id.is_none()) {
+        let Some(start) = raw.start else {
+            continue;
+        };
+        let length = match (raw.end, raw.duration) {
+            (Some(end), _) => end - start,
+            (None, Some(duration)) => duration,
+            (None, None) => Duration::zero(),
+        };
+
+        let Some(occurrences) = expand_occurrences(raw, start, from, until) else {
+            // Listed by the series start, so the missing occurrences don't go unnoticed
+            if start.date() <= until {
+                unexpanded.push(raw.to_event(
+                    start,
+                    start + length,
+                    Some("monthly or yearly recurrence, not expanded"),
+                ));
+            }
+            continue;
+        };
+
+        for occurrence in occurrences {
+            // Modified occurrences come as separate events with the same UID
+            let is_overridden = raw_events
+                .iter()
+                .any(|other| other.uid == raw.uid && other.recurrence_id == Some(occurrence));
+
+            if is_overridden || raw.exdates.contains(&occurrence) {
+                continue;
+            }
+
+            events.push(raw.to_event(occurrence, occurrence + length, unsupported(raw)));
+        }
+    }
+
+    for raw in raw_events.iter().filter(|raw| raw.recurrence_id.is_some()) {
+        if let Some(start) = raw.start {
+            let end = raw.end.unwrap_or(start + raw.duration.unwrap_or_default());
+            events.push(raw.to_event(start, end, unsupported(raw)));
+        }
+    }
+
+    events.retain(|event| event.start.date() >= from && event.start.date() <= until);
+    events.extend(unexpanded);
+    events.sort_by_key(|event| event.start);
+    events
+}
+
+impl RawEvent {
+    fn to_event(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        unsupported: Option<&'static str>,
+    ) -> CalendarEvent {
+        CalendarEvent {
+            summary: self.summary.clone(),
+            organizer: self.organizer.clone(),
+            start,
+            end,
+            all_day: self.all_day,
+            declined: self.declined,
+            cancelled: self.cancelled,
+            unsupported,
+        }
+    }
+}
+
+fn parse_raw_events(content: &str, email: &str) -> Vec<RawEvent> {
+    let mut events: Vec<RawEvent> = Vec::new();
+    let mut current: Option<RawEvent> = None;
+
+    for line in unfold_lines(content) {
+        let Some((name, params, value)) = parse_property(&line) else {
+            continue;
+        };
+
+        match (name.as_str(), current.as_mut()) {
+            ("BEGIN", _) if value == "VEVENT" => current = Some(RawEvent::default()),
+            ("END", Some(_)) if value == "VEVENT" => events.extend(current.take()),
+            ("UID", Some(event)) => event.uid = value,
+            ("SUMMARY", Some(event)) => event.summary = unescape_text(&value),
+            ("ORGANIZER", Some(event)) => event.organizer = Some(strip_mailto(&value)),
+            ("DTSTART", Some(event)) => {
+                event.all_day = is_date_value(&params, &value);
+                event.start = parse_date_time(&params, &value);
+                event.timezone = timezone(&params).filter(|timezone| !is_utc(timezone));
+            }
+            ("DTEND", Some(event)) => event.end = parse_date_time(&params, &value),
+            ("DURATION", Some(event)) => event.duration = parse_ics_duration(&value),
+            ("STATUS", Some(event)) => event.cancelled = value == "CANCELLED",
+            ("RRULE", Some(event)) => event.rrule = Some(value),
+            ("EXDATE", Some(event)) => {
+                event
+                    .exdates
+                    .extend(value.split(',').filter_map(|v| parse_date_time(&params, v)));
+            }
+            ("RECURRENCE-ID", Some(event)) => {
+                event.recurrence_id = parse_date_time(&params, &value)
+            }
+            ("ATTENDEE", Some(event)) => {
+                let is_me = strip_mailto(&value).eq_ignore_ascii_case(email);
+                let declined = params.iter().any(|(key, value)| {
+                    key == "PARTSTAT" && value.eq_ignore_ascii_case("DECLINED")
+                });
+
+                if is_me && declined {
+                    event.declined = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+// Long lines are folded in ICS files, continuation lines start with a space or a tab
+fn unfold_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+// Split a content line into its name, parameters and value, e.g
+// "DTSTART;TZID=Europe/Kyiv:20250407T100000" -> ("DTSTART", [("TZID", "Europe/Kyiv")], "20250407T100000")
+fn parse_property(line: &str) -> Option<Property> {
+    let mut in_quotes = false;
+    let separator = line.char_indices().find(|&(_, c)| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c == ':' && !in_quotes
+    })?;
+
+    let (head, value) = (&line[..separator.0], &line[separator.0 + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.to_uppercase();
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.to_uppercase(), value.trim_matches('"').to_string()))
+        .collect();
+
+    Some((name, params, value.trim().to_string()))
+}
+
+fn is_date_value(params: &[(String, String)], value: &str) -> bool {
+    params
+        .iter()
+        .any(|(key, value)| key == "VALUE" && value.eq_ignore_ascii_case("DATE"))
+        || value.len() == 8
+}
+
+fn timezone(params: &[(String, String)]) -> Option<String> {
+    params
+        .iter()
+        .find(|(key, _)| key == "TZID")
+        .map(|(_, value)| value.clone())
+}
+
+fn is_utc(timezone: &str) -> bool {
+    ["UTC", "Etc/UTC", "GMT", "Etc/GMT"].contains(&timezone)
+}
+
+// Parse ICS date or date-time into local time. UTC values ("Z" suffix or a UTC TZID) are
+// converted to the local timezone, values with other TZIDs are taken as they are
+fn parse_date_time(params: &[(String, String)], value: &str) -> Option<NaiveDateTime> {
+    let utc = value.strip_suffix('Z').or_else(|| {
+        timezone(params)
+            .filter(|timezone| is_utc(timezone))
+            .map(|_| value)
+    });
+
+    if let Some(utc) = utc {
+        let date_time = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(
+            Utc.from_utc_datetime(&date_time)
+                .with_timezone(&Local)
+                .naive_local(),
+        );
+    }
+
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y%m%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+// Parse ICS duration, e.g "PT1H30M" or "P1D"
+fn parse_ics_duration(value: &str) -> Option<Duration> {
+    let value = value.strip_prefix('P')?;
+    let mut duration = Duration::zero();
+    let mut number = String::new();
+
+    for c in value.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let amount: i64 = number.parse().ok()?;
+                number.clear();
+
+                duration += match unit {
+                    'W' => Duration::weeks(amount),
+                    'D' => Duration::days(amount),
+                    'H' => Duration::hours(amount),
+                    'M' => Duration::minutes(amount),
+                    'S' => Duration::seconds(amount),
+                    _ => return None,
+                };
+            }
+        }
+    }
+
+    Some(duration)
+}
+
+fn strip_mailto(value: &str) -> String {
+    let lowercase = value.to_lowercase();
+    lowercase
+        .strip_prefix("mailto:")
+        .unwrap_or(&lowercase)
+        .to_string()
+}
+
+fn unescape_text(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+// Get start times of the occurrences between the dates. Daily and weekly rules are expanded,
+// None for the other rules
+fn expand_occurrences(
+    raw: &RawEvent,
+    start: NaiveDateTime,
+    from: NaiveDate,
+    until: NaiveDate,
+) -> Option<Vec<NaiveDateTime>> {
+    let Some(rrule) = &raw.rrule else {
+        return Some(vec![start]);
+    };
+
+    let rule: Vec<(&str, &str)> = rrule
+        .split(';')
+        .filter_map(|part| part.split_once('='))
+        .collect();
+    let get = |key: &str| rule.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+
+    let interval: i64 = get("INTERVAL")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1)
+        .max(1);
+    let count: usize = get("COUNT")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(usize::MAX);
+    let rule_until = get("UNTIL").and_then(|until| parse_date_time(&[], until));
+    let mut weekdays: Vec<Weekday> = get("BYDAY")
+        .map(|days| days.split(',').filter_map(parse_weekday).collect())
+        .unwrap_or_else(|| vec![start.weekday()]);
+    weekdays.sort_by_key(|day| day.num_days_from_monday());
+    weekdays.dedup();
+
+    let step_days = match get("FREQ") {
+        Some("DAILY") => interval,
+        Some("WEEKLY") => 7 * interval,
+        _ => return None,
+    };
+    let is_weekly = get("FREQ") == Some("WEEKLY");
+
+    let candidates = |period_start: NaiveDateTime| -> Vec<NaiveDateTime> {
+        if is_weekly {
+            let week_start =
+                period_start - Duration::days(period_start.weekday().num_days_from_monday() as i64);
+            weekdays
+                .iter()
+                .map(|day| week_start + Duration::days(day.num_days_from_monday() as i64))
+                .filter(|candidate| *candidate >= start)
+                .collect()
+        } else {
+            vec![period_start]
+        }
+    };
+
+    // Jump over the periods before the dates, only counting their occurrences for COUNT,
+    // so long running series aren't cut off by MAX_OCCURRENCES
+    let skipped_periods = ((from - start.date()).num_days() / step_days - 1).max(0);
+    let mut period_start = start;
+    let mut seen = 0;
+
+    if skipped_periods > 0 {
+        let per_period = if is_weekly { weekdays.len() } else { 1 };
+        seen = candidates(start).len() + (skipped_periods as usize - 1) * per_period;
+        period_start += Duration::days(skipped_periods * step_days);
+    }
+
+    let mut occurrences: Vec<NaiveDateTime> = Vec::new();
+
+    while seen < count && occurrences.len() < MAX_OCCURRENCES && period_start.date() <= until {
+        for candidate in candidates(period_start) {
+            let is_past_until = rule_until.is_some_and(|rule_until| candidate > rule_until);
+
+            if seen >= count || is_past_until || candidate.date() > until {
+                return Some(occurrences);
+            }
+
+            seen += 1;
+            if candidate.date() >= from {
+                occurrences.push(candidate);
+            }
+        }
+
+        period_start += Duration::days(step_days);
+    }
+
+    Some(occurrences)
+}
+
+fn parse_weekday(day: &str) -> Option<Weekday> {
+    match day.trim_start_matches(|c: char| c.is_ascii_digit() || c == '-' || c == '+') {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICS: &str = "BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:standup
+SUMMARY:Daily standup
+DTSTART;TZID=Europe/Kyiv:20250407T100000
+DTEND;TZID=Europe/Kyiv:20250407T101500
+RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR
+EXDATE;TZID=Europe/Kyiv:20250409T100000
+ORGANIZER;CN=\"Lead: Jane\":mailto:lead@example.com
+END:VEVENT
+BEGIN:VEVENT
+UID:standup
+RECURRENCE-ID;TZID=Europe/Kyiv:20250408T100000
+SUMMARY:Daily standup (moved)
+DTSTART;TZID=Europe/Kyiv:20250408T110000
+DTEND;TZID=Europe/Kyiv:20250408T113000
+END:VEVENT
+BEGIN:VEVENT
+UID:review
+SUMMARY:Sprint review\\, demo and
+  retro
+DTSTART:20250410T140000
+DURATION:PT1H30M
+ATTENDEE;PARTSTAT=DECLINED:mailto:Me@Example.com
+END:VEVENT
+BEGIN:VEVENT
+UID:holiday
+SUMMARY:Holiday
+DTSTART;VALUE=DATE:20250411
+DTEND;VALUE=DATE:20250412
+END:VEVENT
+END:VCALENDAR
+";
+
+    fn date_time(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let from = NaiveDate::from_ymd_opt(2025, 4, 1).unwrap();
+        let until = NaiveDate::from_ymd_opt(2025, 4, 11).unwrap();
+        let events = parse_events_in(ICS, "me@example.com", from, until, Some("Europe/Kyiv"));
+
+        let summary: Vec<(String, NaiveDateTime)> = events
+            .iter()
+            .map(|event| (event.summary.clone(), event.start))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("Daily standup".to_string(), date_time("2025-04-07 10:00")),
+                (
+                    "Daily standup (moved)".to_string(),
+                    date_time("2025-04-08 11:00")
+                ),
+                ("Daily standup".to_string(), date_time("2025-04-10 10:00")),
+                (
+                    "Sprint review, demo and retro".to_string(),
+                    date_time("2025-04-10 14:00")
+                ),
+                ("Holiday".to_string(), date_time("2025-04-11 00:00")),
+                ("Daily standup".to_string(), date_time("2025-04-11 10:00")),
+            ]
+        );
+
+        let standup = &events[0];
+        assert_eq!(standup.end, date_time("2025-04-07 10:15"));
+        assert_eq!(standup.organizer, Some("lead@example.com".to_string()));
+        assert!(!standup.declined && !standup.all_day);
+
+        let review = events
+            .iter()
+            .find(|e| e.summary.starts_with("Sprint"))
+            .unwrap();
+        assert_eq!(review.end, date_time("2025-04-10 15:30"));
+        assert!(review.declined);
+
+        let holiday = events.iter().find(|e| e.summary == "Holiday").unwrap();
+        assert!(holiday.all_day);
+    }
+
+    #[test]
+    fn test_parse_events_timezones() {
+        let from = NaiveDate::from_ymd_opt(2025, 4, 1).unwrap();
+        let until = NaiveDate::from_ymd_opt(2025, 4, 11).unwrap();
+
+        // Kyiv times can't be converted to London ones, floating times are local anyway
+        let events = parse_events_in(ICS, "me@example.com", from, until, Some("Europe/London"));
+        let unsupported: Vec<(&str, Option<&str>)> = events
+            .iter()
+            .map(|event| (event.summary.as_str(), event.unsupported))
+            .collect();
+        assert_eq!(
+            unsupported[..4],
+            [
+                ("Daily standup", Some("in another timezone")),
+                ("Daily standup (moved)", Some("in another timezone")),
+                ("Daily standup", Some("in another timezone")),
+                ("Sprint review, demo and retro", None),
+            ]
+        );
+
+        let ics = "BEGIN:VEVENT
+SUMMARY:Sync
+DTSTART;TZID=UTC:20250407T100000
+DTEND;TZID=UTC:20250407T110000
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:Monthly review
+DTSTART:20250303T090000
+DTEND:20250303T100000
+RRULE:FREQ=MONTHLY
+END:VEVENT
+";
+        let events = parse_events_in(ics, "me@example.com", from, until, None);
+        assert_eq!(events.len(), 2);
+
+        // Listed by its first occurrence, before the dates
+        assert_eq!(events[0].summary, "Monthly review");
+        assert_eq!(events[0].start, date_time("2025-03-03 09:00"));
+        assert!(events[0].unsupported.is_some());
+
+        let utc = Utc.with_ymd_and_hms(2025, 4, 7, 10, 0, 0).unwrap();
+        assert_eq!(events[1].start, utc.with_timezone(&Local).naive_local());
+        assert_eq!(events[1].end - events[1].start, Duration::hours(1));
+        assert_eq!(events[1].unsupported, None);
+    }
+
+    #[test]
+    fn test_expand_occurrences() {
+        let start = date_time("2025-04-01 09:00");
+        let from = start.date();
+        let until = NaiveDate::from_ymd_opt(2025, 4, 30).unwrap();
+        let raw = |rrule: &str| RawEvent {
+            rrule: Some(rrule.to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            expand_occurrences(&raw("FREQ=DAILY;COUNT=3"), start, from, until),
+            Some(vec![
+                date_time("2025-04-01 09:00"),
+                date_time("2025-04-02 09:00"),
+                date_time("2025-04-03 09:00")
+            ])
+        );
+        assert_eq!(
+            expand_occurrences(
+                &raw("FREQ=WEEKLY;INTERVAL=2;UNTIL=20250420T000000Z"),
+                start,
+                from,
+                until
+            ),
+            Some(vec![
+                date_time("2025-04-01 09:00"),
+                date_time("2025-04-15 09:00")
+            ])
+        );
+        assert_eq!(
+            expand_occurrences(&raw("FREQ=WEEKLY;BYDAY=MO,WE;COUNT=3"), start, from, until),
+            Some(vec![
+                date_time("2025-04-02 09:00"),
+                date_time("2025-04-07 09:00"),
+                date_time("2025-04-09 09:00")
+            ])
+        );
+        assert_eq!(
+            expand_occurrences(&raw("FREQ=DAILY"), start, from, start.date()),
+            Some(vec![start])
+        );
+        assert_eq!(
+            expand_occurrences(&raw("FREQ=MONTHLY"), start, from, until),
+            None
+        );
+
+        // Only the occurrences within the dates are expanded, however long the series runs
+        let date = |value: &str| NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap();
+        assert_eq!(
+            expand_occurrences(
+                &raw("FREQ=DAILY"),
+                date_time("2005-01-03 09:00"),
+                date("2025-04-07"),
+                date("2025-04-08")
+            ),
+            Some(vec![
+                date_time("2025-04-07 09:00"),
+                date_time("2025-04-08 09:00")
+            ])
+        );
+        assert_eq!(
+            expand_occurrences(
+                &raw("FREQ=WEEKLY;BYDAY=MO,WE"),
+                date_time("2005-01-05 09:00"),
+                date("2025-04-07"),
+                date("2025-04-13")
+            ),
+            Some(vec![
+                date_time("2025-04-07 09:00"),
+                date_time("2025-04-09 09:00")
+            ])
+        );
+        // Occurrences before the dates still count towards COUNT
+        assert_eq!(
+            expand_occurrences(
+                &raw("FREQ=WEEKLY;BYDAY=MO,WE;COUNT=30"),
+                date_time("2025-01-01 09:00"),
+                date("2025-04-14"),
+                until
+            ),
+            Some(vec![date_time("2025-04-14 09:00")])
+        );
+    }
+
+    #[test]
+    fn test_parse_ics_duration() {
+        assert_eq!(parse_ics_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_ics_duration("P1D"), Some(Duration::days(1)));
+        assert_eq!(parse_ics_duration("PT45S"), Some(Duration::seconds(45)));
+        assert_eq!(parse_ics_duration("1H"), None);
+    }
+
+    #[test]
+    fn test_match_issue() {
+        let rules = CalendarRules {
+            default: Some("INTERNAL-1".to_string()),
+            rules: vec![
+                CalendarRule {
+                    summary: Some("(?i)standup".to_string()),
+                    organizer: None,
+                    issue: "standup".to_string(),
+                },
+                CalendarRule {
+                    summary: None,
+                    organizer: Some("@client\\.com$".to_string()),
+                    issue: "CLIENT-7".to_string(),
+                },
+            ],
+        };
+        let event = |summary: &str, organizer: Option<&str>| CalendarEvent {
+            summary: summary.to_string(),
+            organizer: organizer.map(String::from),
+            start: date_time("2025-04-07 10:00"),
+            end: date_time("2025-04-07 11:00"),
+            all_day: false,
+            declined: false,
+            cancelled: false,
+            unsupported: None,
+        };
+
+        assert_eq!(
+            rules.match_issue(&event("Daily Standup", None)),
+            Ok(Some("standup".to_string()))
+        );
+        assert_eq!(
+            rules.match_issue(&event("Weekly sync", Some("pm@client.com"))),
+            Ok(Some("CLIENT-7".to_string()))
+        );
+        assert_eq!(
+            rules.match_issue(&event("Weekly sync", None)),
+            Ok(Some("INTERNAL-1".to_string()))
+        );
+        assert_eq!(
+            CalendarRules::default().match_issue(&event("Weekly sync", None)),
+            Ok(None)
+        );
+
+        let invalid = CalendarRules {
+            default: None,
+            rules: vec![CalendarRule {
+                summary: Some("(".to_string()),
+                organizer: None,
+                issue: "ABC-1".to_string(),
+            }],
+        };
+        assert!(invalid.match_issue(&event("Weekly sync", None)).is_err());
+    }
+
+    #[test]
+    fn test_load_rules() {
+        let test_rules_path = "test_load_calendar_rules.toml";
+        std::fs::write(
+            test_rules_path,
+            "default = \"INTERNAL-1\"\n\n[[rules]]\nsummary = \"(?i)standup\"\nissue = \"standup\"\n",
+        )
+        .unwrap();
+
+        let rules = CalendarRules::load(Some(Path::new(test_rules_path))).unwrap();
+        assert_eq!(rules.default, Some("INTERNAL-1".to_string()));
+        assert_eq!(rules.rules.len(), 1);
+        assert_eq!(rules.rules[0].summary, Some("(?i)standup".to_string()));
+        assert_eq!(rules.rules[0].issue, "standup");
+
+        assert!(CalendarRules::load(Some(Path::new("missing.toml"))).is_err());
+
+        let _ = std::fs::remove_file(test_rules_path);
+    }
+}
@@ -0,0 +1,169 @@
+use super::{
+    parse_clock_duration, parse_date, parse_decimal_hours, parse_time, split_tags, CsvExport,
+    EntryReader, TimeEntry,
+};
+use chrono::NaiveDate;
+
+// Dates follow the workspace settings, so the format is detected from the whole file
+const DATE_FORMATS: [&str; 4] = ["%m/%d/%Y", "%Y-%m-%d", "%d.%m.%Y", "%d/%m/%Y"];
+
+// Reads the Clockify detailed report CSV export
+pub struct ClockifyReader {
+    // Format of the dates, detected when not given
+    pub date_format: Option<String>,
+}
+
+impl EntryReader for ClockifyReader {
+    fn read(&self, content: &str) -> Result<Vec<TimeEntry>, String> {
+        let export = CsvExport::parse(content)?;
+        let dates = export
+            .records
+            .iter()
+            .map(|record| export.required_field(record, &["start date"]))
+            .collect::<Result<Vec<_>, _>>()?;
+        let date_format = match &self.date_format {
+            Some(date_format) => date_format.as_str(),
+            None => detect_date_format(&dates)?,
+        };
+
+        export
+            .records
+            .iter()
+            .zip(dates)
+            .map(|(record, date)| {
+                let duration_seconds = export
+                    .field(record, &["duration (h)"])
+                    .and_then(parse_clock_duration)
+                    .or_else(|| {
+                        export
+                            .field(record, &["duration (decimal)"])
+                            .and_then(parse_decimal_hours)
+                    })
+                    .ok_or("Missing or invalid column 'Duration (h)'")?;
+
+                Ok(TimeEntry {
+                    date: NaiveDate::parse_from_str(date, date_format)
+                        .map_err(|_| format!("Invalid date: '{}'", date))?,
+                    start_time: export.field(record, &["start time"]).and_then(parse_time),
+                    duration_seconds,
+                    description: export
+                        .field(record, &["description"])
+                        .unwrap_or_default()
+                        .to_string(),
+                    project: export
+                        .field(record, &["project"])
+                        .unwrap_or_default()
+                        .to_string(),
+                    tags: split_tags(export.field(record, &["tags"])),
+                })
+            })
+            .collect()
+    }
+}
+
+// Pick the one format all the dates are in. A file with dates like 04/07/2025 only can't tell
+// month/day from day/month, it's rejected rather than risk logging on the wrong days
+fn detect_date_format(dates: &[&str]) -> Result<&'static str, String> {
+    if let Some(date) = dates
+        .iter()
+        .find(|date| parse_date(date, &DATE_FORMATS).is_none())
+    {
+        return Err(format!("Invalid date: '{}'", date));
+    }
+
+    let formats: Vec<&str> = DATE_FORMATS
+        .into_iter()
+        .filter(|format| {
+            dates
+                .iter()
+                .all(|date| NaiveDate::parse_from_str(date, format).is_ok())
+        })
+        .collect();
+
+    match formats.as_slice() {
+        [format] => Ok(format),
+        [] => Err("The dates are in different formats, use --date-format".to_string()),
+        // Nothing to import, any format will do
+        _ if dates.is_empty() => Ok(DATE_FORMATS[0]),
+        _ => Err(format!(
+            "Ambiguous dates, they read as {}. Use --date-format to pick one",
+            formats.join(" and ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn test_read() {
+        let content = "Project,Client,Description,Task,User,Group,Email,Tags,Billable,Start Date,Start Time,End Date,End Time,Duration (h),Duration (decimal),Billable Rate (USD),Billable Amount (USD)
+Website,Acme,Fix header,,Jane,,jane@example.com,meeting,Yes,04/07/2025,02:00:00 PM,04/07/2025,03:30:00 PM,01:30:00,1.50,0,0
+Internal,,Planning,,Jane,,jane@example.com,,No,04/18/2025,09:00:00 AM,04/18/2025,09:45:00 AM,,0.75,0,0
+";
+
+        let entries = ClockifyReader { date_format: None }.read(content).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].date,
+            NaiveDate::from_ymd_opt(2025, 4, 7).unwrap()
+        );
+        assert_eq!(entries[0].start_time, NaiveTime::from_hms_opt(14, 0, 0));
+        assert_eq!(entries[0].duration_seconds, 5400);
+        assert_eq!(entries[0].description, "Fix header");
+        assert_eq!(entries[0].project, "Website");
+        assert_eq!(entries[0].tags, vec!["meeting".to_string()]);
+
+        assert_eq!(
+            entries[1].date,
+            NaiveDate::from_ymd_opt(2025, 4, 18).unwrap()
+        );
+        assert_eq!(entries[1].start_time, NaiveTime::from_hms_opt(9, 0, 0));
+        assert_eq!(entries[1].duration_seconds, 2700);
+        assert!(entries[1].tags.is_empty());
+    }
+
+    #[test]
+    fn test_read_date_format() {
+        let content = |dates: &[&str]| {
+            let mut content = "Project,Description,Start Date,Duration (h)\n".to_string();
+            for date in dates {
+                content.push_str(&format!("Website,Fix header,{},01:00:00\n", date));
+            }
+            content
+        };
+        let read = |dates: &[&str], date_format: Option<&str>| {
+            ClockifyReader {
+                date_format: date_format.map(str::to_string),
+            }
+            .read(&content(dates))
+            .map(|entries| entries.iter().map(|e| e.date).collect::<Vec<_>>())
+        };
+        let date = |month, day| NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+
+        // One day first date is enough to read the whole file day first
+        assert_eq!(
+            read(&["04/07/2025", "18/04/2025"], None),
+            Ok(vec![date(7, 4), date(4, 18)])
+        );
+        assert_eq!(
+            read(&["07.04.2025", "18.04.2025"], None),
+            Ok(vec![date(4, 7), date(4, 18)])
+        );
+
+        assert!(read(&["04/07/2025", "04/08/2025"], None)
+            .unwrap_err()
+            .starts_with("Ambiguous dates"));
+        assert_eq!(
+            read(&["04/07/2025", "04/08/2025"], Some("%d/%m/%Y")),
+            Ok(vec![date(7, 4), date(8, 4)])
+        );
+
+        assert!(read(&["04/18/2025", "18/04/2025"], None).is_err());
+        assert!(read(&["2025/04/07"], None).is_err());
+        assert!(read(&["04/07/2025"], Some("%Y-%m-%d")).is_err());
+    }
+}
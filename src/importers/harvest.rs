@@ -0,0 +1,73 @@
+use super::{parse_date, parse_decimal_hours, CsvExport, EntryReader, TimeEntry};
+
+// Reads the Harvest detailed time report CSV export. Harvest has no tags,
+// the task is used as a tag instead, and entries have no start time
+pub struct HarvestReader;
+
+impl EntryReader for HarvestReader {
+    fn read(&self, content: &str) -> Result<Vec<TimeEntry>, String> {
+        let export = CsvExport::parse(content)?;
+
+        export
+            .records
+            .iter()
+            .map(|record| {
+                let date = export.required_field(record, &["date"])?;
+                let hours = export.required_field(record, &["hours"])?;
+
+                Ok(TimeEntry {
+                    date: parse_date(date, &["%Y-%m-%d", "%m/%d/%Y"])
+                        .ok_or_else(|| format!("Invalid date: '{}'", date))?,
+                    start_time: None,
+                    duration_seconds: parse_decimal_hours(hours)
+                        .ok_or_else(|| format!("Invalid hours: '{}'", hours))?,
+                    description: export
+                        .field(record, &["notes"])
+                        .unwrap_or_default()
+                        .to_string(),
+                    project: export
+                        .field(record, &["project"])
+                        .unwrap_or_default()
+                        .to_string(),
+                    tags: export
+                        .field(record, &["task"])
+                        .filter(|task| !task.is_empty())
+                        .map(|task| vec![task.to_string()])
+                        .unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_read() {
+        let content = "Date,Client,Project,Project Code,Task,Notes,Hours,Hours Rounded,Billable?,Invoiced?,Approved?,First Name,Last Name,Roles,Employee?
+2025-04-07,Acme,Website,WEB,Development,Fix header,1.5,1.5,Yes,No,No,Jane,Doe,,Yes
+2025-04-08,Acme,Website,WEB,,,0.25,0.25,Yes,No,No,Jane,Doe,,Yes
+";
+
+        let entries = HarvestReader.read(content).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].date,
+            NaiveDate::from_ymd_opt(2025, 4, 7).unwrap()
+        );
+        assert_eq!(entries[0].start_time, None);
+        assert_eq!(entries[0].duration_seconds, 5400);
+        assert_eq!(entries[0].description, "Fix header");
+        assert_eq!(entries[0].project, "Website");
+        assert_eq!(entries[0].tags, vec!["Development".to_string()]);
+
+        assert_eq!(entries[1].duration_seconds, 900);
+        assert!(entries[1].tags.is_empty());
+
+        assert!(HarvestReader.read("Date,Hours\n2025-04-07,many\n").is_err());
+    }
+}
@@ -0,0 +1,266 @@
+pub mod calendar;
+pub mod clockify;
+pub mod harvest;
+pub mod toggl;
+
+use crate::storage::Storage;
+use chrono::{NaiveDate, NaiveTime};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+const DEFAULT_MAPPING_FILE: &str = "import.toml";
+
+// Time entry read from an export of another time tracking tool
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeEntry {
+    pub date: NaiveDate,
+    pub start_time: Option<NaiveTime>,
+    pub duration_seconds: i32,
+    pub description: String,
+    pub project: String,
+    pub tags: Vec<String>,
+}
+
+// Reader of time tracking tool exports. Add a new implementation to support another tool
+pub trait EntryReader {
+    fn read(&self, content: &str) -> Result<Vec<TimeEntry>, String>;
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ImportSource {
+    Toggl,
+    Clockify,
+    Harvest,
+}
+
+impl ImportSource {
+    // Only Clockify exports come with a date format of the user's choice
+    pub fn reader(&self, date_format: Option<String>) -> Box<dyn EntryReader> {
+        match self {
+            ImportSource::Toggl => Box::new(toggl::TogglReader),
+            ImportSource::Clockify => Box::new(clockify::ClockifyReader { date_format }),
+            ImportSource::Harvest => Box::new(harvest::HarvestReader),
+        }
+    }
+}
+
+// Maps projects and tags of imported entries to Jira issue keys or aliases
+#[derive(Debug, Deserialize, Default)]
+pub struct ImportMapping {
+    pub default: Option<String>,
+    #[serde(default)]
+    pub projects: HashMap<String, String>,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+}
+
+impl ImportMapping {
+    // Load the mapping file given by the user or the default one, if it exists
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        load_file(path, DEFAULT_MAPPING_FILE, "import mapping")
+    }
+
+    // Tags are more specific than projects, so a mapped tag wins over the project
+    pub fn match_issue(&self, entry: &TimeEntry) -> Option<String> {
+        entry
+            .tags
+            .iter()
+            .find_map(|tag| self.tags.get(tag))
+            .or_else(|| self.projects.get(&entry.project))
+            .or(self.default.as_ref())
+            .cloned()
+    }
+}
+
+// Load a TOML, JSON or YAML file. Without a path the default file in ~/.tempie is loaded,
+// falling back to the defaults when it doesn't exist
+pub fn load_file<T: DeserializeOwned + Default>(
+    path: Option<&Path>,
+    default_file: &str,
+    description: &str,
+) -> Result<T, String> {
+    let default_path = Storage::get_db_path(default_file);
+    let path = match path {
+        Some(path) => path,
+        None if default_path.exists() => &default_path,
+        None => return Ok(T::default()),
+    };
+
+    config::Config::builder()
+        .add_source(config::File::from(path))
+        .build()
+        .and_then(|file| file.try_deserialize())
+        .map_err(|e| format!("Failed to read {} {}: {}", description, path.display(), e))
+}
+
+// CSV export with columns looked up by their header names
+struct CsvExport {
+    headers: Vec<String>,
+    records: Vec<csv::StringRecord>,
+}
+
+impl CsvExport {
+    fn parse(content: &str) -> Result<Self, String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(content.trim_start_matches('\u{feff}').as_bytes());
+
+        let headers = reader
+            .headers()
+            .map_err(|e| format!("Failed to read CSV headers: {}", e))?
+            .iter()
+            .map(|header| header.trim().to_lowercase())
+            .collect();
+
+        let records = reader
+            .records()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read CSV: {}", e))?;
+
+        Ok(Self { headers, records })
+    }
+
+    // Get the value of the first column present in the export
+    fn field<'a>(&self, record: &'a csv::StringRecord, columns: &[&str]) -> Option<&'a str> {
+        columns
+            .iter()
+            .filter_map(|column| self.headers.iter().position(|h| h == column))
+            .find_map(|index| record.get(index))
+            .map(str::trim)
+    }
+
+    fn required_field<'a>(
+        &self,
+        record: &'a csv::StringRecord,
+        columns: &[&str],
+    ) -> Result<&'a str, String> {
+        self.field(record, columns).ok_or_else(|| {
+            format!(
+                "Missing column '{}' on line {}",
+                columns[0],
+                record.position().map(|p| p.line()).unwrap_or_default()
+            )
+        })
+    }
+}
+
+// Parse duration in "HH:MM:SS" or "HH:MM" format
+fn parse_clock_duration(value: &str) -> Option<i32> {
+    let parts: Vec<i32> = value
+        .split(':')
+        .map(|part| part.trim().parse().ok())
+        .collect::<Option<_>>()?;
+
+    match parts.as_slice() {
+        [hours, minutes, seconds] => Some(hours * 3600 + minutes * 60 + seconds),
+        [hours, minutes] => Some(hours * 3600 + minutes * 60),
+        _ => None,
+    }
+}
+
+// Parse duration in decimal hours, e.g "1.5" -> 5400
+fn parse_decimal_hours(value: &str) -> Option<i32> {
+    value
+        .trim()
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .map(|hours| (hours * 3600.0).round() as i32)
+}
+
+fn parse_date(value: &str, formats: &[&str]) -> Option<NaiveDate> {
+    formats
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    ["%H:%M:%S", "%H:%M", "%I:%M:%S %p", "%I:%M %p"]
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(value, format).ok())
+}
+
+fn split_tags(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(project: &str, tags: &[&str]) -> TimeEntry {
+        TimeEntry {
+            date: NaiveDate::from_ymd_opt(2025, 4, 7).unwrap(),
+            start_time: None,
+            duration_seconds: 3600,
+            description: "Work".to_string(),
+            project: project.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_match_issue() {
+        let mapping = ImportMapping {
+            default: Some("INTERNAL-1".to_string()),
+            projects: HashMap::from([("Website".to_string(), "WEB-1".to_string())]),
+            tags: HashMap::from([("meeting".to_string(), "standup".to_string())]),
+        };
+
+        assert_eq!(
+            mapping.match_issue(&entry("Website", &[])),
+            Some("WEB-1".to_string())
+        );
+        assert_eq!(
+            mapping.match_issue(&entry("Website", &["billable", "meeting"])),
+            Some("standup".to_string())
+        );
+        assert_eq!(
+            mapping.match_issue(&entry("Other", &[])),
+            Some("INTERNAL-1".to_string())
+        );
+        assert_eq!(
+            ImportMapping::default().match_issue(&entry("Other", &[])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_load_mapping() {
+        let test_mapping_path = "test_load_import_mapping.toml";
+        std::fs::write(
+            test_mapping_path,
+            "default = \"INTERNAL-1\"\n\n[projects]\n\"Website Redesign\" = \"WEB-1\"\n\n[tags]\nMeeting = \"standup\"\n",
+        )
+        .unwrap();
+
+        let mapping = ImportMapping::load(Some(Path::new(test_mapping_path))).unwrap();
+        assert_eq!(mapping.default, Some("INTERNAL-1".to_string()));
+        assert_eq!(
+            mapping.projects.get("Website Redesign"),
+            Some(&"WEB-1".to_string())
+        );
+        assert_eq!(mapping.tags.get("Meeting"), Some(&"standup".to_string()));
+
+        let _ = std::fs::remove_file(test_mapping_path);
+    }
+
+    #[test]
+    fn test_parse_durations() {
+        assert_eq!(parse_clock_duration("01:30:00"), Some(5400));
+        assert_eq!(parse_clock_duration("0:45"), Some(2700));
+        assert_eq!(parse_clock_duration("1h"), None);
+
+        assert_eq!(parse_decimal_hours("1.5"), Some(5400));
+        assert_eq!(parse_decimal_hours("0,25"), Some(900));
+        assert_eq!(parse_decimal_hours("abc"), None);
+    }
+}
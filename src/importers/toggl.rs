@@ -0,0 +1,80 @@
+use super::{
+    parse_clock_duration, parse_date, parse_time, split_tags, CsvExport, EntryReader, TimeEntry,
+};
+
+// Reads the Toggl Track detailed report CSV export
+pub struct TogglReader;
+
+impl EntryReader for TogglReader {
+    fn read(&self, content: &str) -> Result<Vec<TimeEntry>, String> {
+        let export = CsvExport::parse(content)?;
+
+        export
+            .records
+            .iter()
+            .map(|record| {
+                let date = export.required_field(record, &["start date"])?;
+                let duration = export.required_field(record, &["duration"])?;
+
+                Ok(TimeEntry {
+                    date: parse_date(date, &["%Y-%m-%d"])
+                        .ok_or_else(|| format!("Invalid date: '{}'", date))?,
+                    start_time: export.field(record, &["start time"]).and_then(parse_time),
+                    duration_seconds: parse_clock_duration(duration)
+                        .ok_or_else(|| format!("Invalid duration: '{}'", duration))?,
+                    description: export
+                        .field(record, &["description"])
+                        .unwrap_or_default()
+                        .to_string(),
+                    project: export
+                        .field(record, &["project"])
+                        .unwrap_or_default()
+                        .to_string(),
+                    tags: split_tags(export.field(record, &["tags"])),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn test_read() {
+        let content = "User,Email,Client,Project,Task,Description,Billable,Start date,Start time,End date,End time,Duration,Tags,Amount ()
+Jane,jane@example.com,Acme,Website,,Fix header,Yes,2025-04-07,09:00:00,2025-04-07,10:30:00,01:30:00,\"design, frontend\",
+Jane,jane@example.com,,Internal,,,No,2025-04-08,14:00:00,2025-04-08,14:15:00,00:15:00,,
+";
+
+        let entries = TogglReader.read(content).unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                TimeEntry {
+                    date: NaiveDate::from_ymd_opt(2025, 4, 7).unwrap(),
+                    start_time: NaiveTime::from_hms_opt(9, 0, 0),
+                    duration_seconds: 5400,
+                    description: "Fix header".to_string(),
+                    project: "Website".to_string(),
+                    tags: vec!["design".to_string(), "frontend".to_string()],
+                },
+                TimeEntry {
+                    date: NaiveDate::from_ymd_opt(2025, 4, 8).unwrap(),
+                    start_time: NaiveTime::from_hms_opt(14, 0, 0),
+                    duration_seconds: 900,
+                    description: "".to_string(),
+                    project: "Internal".to_string(),
+                    tags: vec![],
+                },
+            ]
+        );
+
+        assert!(TogglReader
+            .read("Project,Description\nWebsite,Fix\n")
+            .is_err());
+    }
+}
@@ -1,5 +1,10 @@
 pub mod api;
 pub mod commands;
+pub mod git;
+pub mod importers;
 pub mod models;
+pub mod settings;
 pub mod storage;
 pub mod utils;
+pub mod validation;
+pub mod validators;
@@ -1,18 +1,33 @@
 mod api;
 mod commands;
+mod git;
+mod importers;
 mod models;
+mod settings;
 mod storage;
 mod utils;
+mod validation;
 mod validators;
+use crate::commands::{CreateOptions, MirrorArgs, RemainingArgs, WorklogArgs};
+use crate::importers::ImportSource;
+use crate::models::{TimesheetReview, WorklogChanges};
+use crate::settings::Settings;
 use crate::storage::Storage;
-use crate::utils::{ensure_credentials_exist, today_as_iso8601};
-use clap::{Parser, Subcommand};
+use crate::utils::{ensure_credentials_exist, get_first_day_of_month, today_as_iso8601};
+use clap::{CommandFactory, Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    #[arg(
+        long,
+        global = true,
+        help = "List the worklogs of another user, by email or Jira account ID"
+    )]
+    user: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -27,6 +42,8 @@ enum Commands {
             value_parser = validators::validate_iso8601_date
         )]
         date: String,
+        #[command(flatten)]
+        mirror_args: MirrorArgs,
     },
     /// List worklogs by date range
     ListRange {
@@ -40,23 +57,390 @@ enum Commands {
             value_parser = validators::validate_iso8601_date
         )]
         date_to: String,
+        #[command(flatten)]
+        mirror_args: MirrorArgs,
+    },
+    /// List everyone's worklogs on an issue
+    IssueLog {
+        #[arg(help = "The Jira issue key or alias (e.g., XXX-123)")]
+        issue_key: String,
+        #[arg(
+            long = "from",
+            help = "The date to list worklogs from (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_from: Option<String>,
+        #[arg(
+            long = "to",
+            help = "The date to list worklogs to (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_to: Option<String>,
+    },
+    /// List everyone's worklogs on a project
+    ProjectLog {
+        #[arg(help = "The Jira project key (e.g., XXX)")]
+        project_key: String,
+        #[arg(
+            long = "from",
+            default_value_t = get_first_day_of_month(&today_as_iso8601()),
+            help = "The date to list worklogs from (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_from: String,
+        #[arg(
+            long = "to",
+            default_value_t = today_as_iso8601(),
+            help = "The date to list worklogs to (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_to: String,
     },
     /// Log time
     Log {
-        #[arg(help = "The Jira issue key to log time against (e.g., XXX-123)")]
-        issue_key: String,
-        #[arg(help = "The time spent to log (e.g., 1h30m)")]
-        time_spent: String,
+        #[arg(
+            help = "The Jira issue key to log time against (e.g., XXX-123). Use a comma separated list to split the time equally between issues. Omit it or use '.' to take the key from the current git branch, or pick one when there's none"
+        )]
+        issue_key: Option<String>,
+        #[arg(help = "The time spent to log (e.g., 1h30m). Optional if the alias has a duration")]
+        time_spent: Option<String>,
         #[arg(help = "The comment to add to the worklog. Optional.")]
         comment: Option<String>,
+        #[arg(
+            long,
+            value_name = "ISSUES",
+            help = "Split the time by percentage between issues instead of giving the issue key (e.g., XXX-1:50%,XXX-2:50%)"
+        )]
+        split: Option<String>,
+        #[arg(
+            long,
+            help = "Also look for the issue key in recent commit messages when it's omitted"
+        )]
+        from_commits: bool,
+        #[command(flatten)]
+        worklog_args: WorklogArgs,
+        #[command(flatten)]
+        remaining_args: RemainingArgs,
+        #[command(flatten)]
+        create_options: CreateOptions,
+    },
+    /// Edit a worklog
+    Edit {
+        #[arg(help = "Worklog ID to edit")]
+        worklog_id: String,
+        #[arg(
+            long = "time",
+            help = "The new time spent (e.g., 1h30m)",
+            value_parser = validators::validate_duration
+        )]
+        time_spent: Option<String>,
+        #[arg(long, help = "The new comment")]
+        comment: Option<String>,
+        #[arg(
+            long,
+            help = "The new date (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date: Option<String>,
+        #[arg(long, help = "The new start time (format: HH:MM)")]
+        start_time: Option<String>,
+        #[command(flatten)]
+        worklog_args: WorklogArgs,
+        #[arg(
+            long,
+            help = "Update the worklog even if it breaks the validation rules"
+        )]
+        force: bool,
     },
     /// Delete worklog(s)
     Delete {
         #[arg(help = "Worklog ID(s) to delete", num_args = 1..)]
         worklog_ids: Vec<String>,
+        #[arg(long, help = "Delete even if the worklogs are in an approved period")]
+        force: bool,
+    },
+    /// Suggest worklogs based on git commit history
+    Suggest {
+        #[arg(
+            long = "from",
+            default_value_t = today_as_iso8601(),
+            help = "The date to look for commits from (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_from: String,
+        #[arg(
+            long = "to",
+            default_value_t = today_as_iso8601(),
+            help = "The date to look for commits to (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_to: String,
+        #[arg(
+            long = "repo",
+            num_args = 1..,
+            default_value = ".",
+            help = "Path(s) to local git repositories"
+        )]
+        repos: Vec<PathBuf>,
+        #[arg(long, help = "The commit author email. Defaults to your Jira email")]
+        author: Option<String>,
+        #[arg(
+            long,
+            default_value = "2h",
+            help = "The longest time credited to a single commit (e.g., 1h30m)",
+            value_parser = validators::validate_duration
+        )]
+        session_cap: String,
+        #[arg(long, help = "Create the suggested worklogs without asking")]
+        yes: bool,
+        #[command(flatten)]
+        create_options: CreateOptions,
+    },
+    /// Import time entries from an ICS calendar file
+    ImportCalendar {
+        #[arg(help = "Path to the ICS file")]
+        file: PathBuf,
+        #[arg(
+            long = "from",
+            default_value_t = today_as_iso8601(),
+            help = "The date to import events from (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_from: String,
+        #[arg(
+            long = "to",
+            default_value_t = today_as_iso8601(),
+            help = "The date to import events to (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_to: String,
+        #[arg(
+            long = "rules",
+            help = "Path to the file with rules mapping events to issues. Defaults to ~/.tempie/calendar.toml"
+        )]
+        rules_file: Option<PathBuf>,
+        #[arg(
+            long = "default",
+            help = "The issue key or alias for events no rule matches"
+        )]
+        default_issue: Option<String>,
+        #[arg(long, help = "Create the worklogs without asking")]
+        yes: bool,
+        #[command(flatten)]
+        create_options: CreateOptions,
+    },
+    /// Import time entries from Toggl, Clockify or Harvest CSV exports
+    Import {
+        #[arg(value_enum, help = "The tool the CSV was exported from")]
+        source: ImportSource,
+        #[arg(help = "Path to the CSV file")]
+        file: PathBuf,
+        #[arg(
+            long = "from",
+            help = "The date to import entries from (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_from: Option<String>,
+        #[arg(
+            long = "to",
+            help = "The date to import entries to (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_to: Option<String>,
+        #[arg(
+            long = "mapping",
+            help = "Path to the file mapping projects and tags to issues. Defaults to ~/.tempie/import.toml"
+        )]
+        mapping_file: Option<PathBuf>,
+        #[arg(
+            long = "default",
+            help = "The issue key or alias for entries with no mapping"
+        )]
+        default_issue: Option<String>,
+        #[arg(
+            long,
+            help = "The date format of Clockify exports (e.g., %d/%m/%Y). Detected from the dates by default"
+        )]
+        date_format: Option<String>,
+        #[arg(long, help = "Create the worklogs without asking")]
+        yes: bool,
+        #[command(flatten)]
+        create_options: CreateOptions,
+    },
+    /// Check existing worklogs against the validation rules
+    Lint {
+        #[arg(
+            long = "from",
+            default_value_t = get_first_day_of_month(&today_as_iso8601()),
+            help = "The date to check worklogs from (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_from: String,
+        #[arg(
+            long = "to",
+            default_value_t = today_as_iso8601(),
+            help = "The date to check worklogs to (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date_to: String,
+    },
+    /// Submit the timesheet of a period for approval
+    Submit {
+        #[arg(
+            long = "period",
+            default_value_t = today_as_iso8601(),
+            help = "A date in the period to submit (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date: String,
+        #[arg(long, help = "The Jira account ID of the reviewer")]
+        reviewer: Option<String>,
+        #[arg(long, help = "A comment for the reviewer")]
+        comment: Option<String>,
+    },
+    /// Show the approval status of a timesheet period
+    ApprovalStatus {
+        #[arg(
+            long = "period",
+            default_value_t = today_as_iso8601(),
+            help = "A date in the period to show (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date: String,
+    },
+    /// Review the timesheets of your teams
+    Team {
+        #[command(subcommand)]
+        command: TeamCommands,
+    },
+    /// Show the worklogs tempie created, edited and deleted
+    History {
+        #[arg(long, default_value_t = 20, help = "The number of operations to show")]
+        limit: usize,
+    },
+    /// Revert the last operations from the history
+    Undo {
+        #[arg(default_value_t = 1, help = "The number of operations to revert")]
+        count: usize,
+        #[arg(long, help = "Revert even if the worklogs are in an approved period")]
+        force: bool,
+    },
+    /// Replay the changes queued while the network was unavailable
+    Sync {
+        #[arg(long, help = "Apply the changes even if the worklogs changed since")]
+        force: bool,
+        #[arg(
+            long,
+            value_name = "ID",
+            num_args = 1..,
+            help = "Drop queued changes by their number instead of replaying them"
+        )]
+        discard: Vec<u64>,
+    },
+    /// Search Jira issues by text or JQL
+    Search {
+        #[arg(help = "The text to search for or a JQL query")]
+        query: String,
+        #[arg(long, help = "Use the query as JQL, even if it doesn't look like it")]
+        jql: bool,
+        #[arg(
+            long,
+            default_value_t = 20,
+            help = "The maximum number of issues to show"
+        )]
+        limit: u32,
+    },
+    /// List Tempo work attributes
+    Attributes {
+        #[arg(long, help = "Fetch the attributes from Tempo instead of the cache")]
+        refresh: bool,
     },
     /// Clean jira issues from database
     CleanJiraIssues,
+    /// Manage issue key aliases
+    Alias {
+        #[command(subcommand)]
+        command: AliasCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum TeamCommands {
+    /// List teams and their members
+    List,
+    /// Show each member's logged and required time per day of a week
+    Week {
+        #[arg(help = "The team ID or name")]
+        team: String,
+        #[arg(
+            default_value_t = today_as_iso8601(),
+            help = "A date in the week to show (format: YYYY-MM-DD)",
+            value_parser = validators::validate_iso8601_date
+        )]
+        date: String,
+    },
+    /// Approve a member's timesheet
+    Approve {
+        #[command(flatten)]
+        review: ReviewArgs,
+    },
+    /// Reject a member's timesheet
+    Reject {
+        #[command(flatten)]
+        review: ReviewArgs,
+    },
+}
+
+#[derive(clap::Args)]
+struct ReviewArgs {
+    #[arg(help = "The Jira account ID of the member")]
+    user: String,
+    #[arg(
+        long = "period",
+        default_value_t = today_as_iso8601(),
+        help = "A date in the period to review (format: YYYY-MM-DD)",
+        value_parser = validators::validate_iso8601_date
+    )]
+    date: String,
+    #[arg(long, help = "A comment for the member")]
+    comment: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum AliasCommands {
+    /// Add an alias or overwrite the existing one
+    Add {
+        #[arg(help = "The alias name (e.g., standup)")]
+        name: String,
+        #[arg(help = "The Jira issue key the alias points to (e.g., XXX-123)")]
+        issue_key: String,
+        #[arg(
+            long = "desc",
+            help = "Default comment for worklogs logged with the alias"
+        )]
+        description: Option<String>,
+        #[arg(
+            long,
+            help = "Default time spent for worklogs logged with the alias (e.g., 15m)",
+            value_parser = validators::validate_duration
+        )]
+        duration: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "non_billable",
+            help = "Always log the alias as billable"
+        )]
+        billable: bool,
+        #[arg(long, help = "Always log the alias as non-billable")]
+        non_billable: bool,
+    },
+    /// List aliases
+    List,
+    /// Remove an alias
+    Remove {
+        #[arg(help = "The alias name")]
+        name: String,
+    },
 }
 
 #[tokio::main]
@@ -64,9 +448,53 @@ async fn main() {
     let cli = Cli::parse();
     let storage = Storage::new();
 
+    // Other users' worklogs aren't mirrored. Clap only catches the conflict when --user comes
+    // after the subcommand
+    if let Commands::List { mirror_args, .. } | Commands::ListRange { mirror_args, .. } =
+        &cli.command
+    {
+        if cli.user.is_some() && (mirror_args.offline || mirror_args.refresh) {
+            Cli::command()
+                .error(
+                    clap::error::ErrorKind::ArgumentConflict,
+                    "the argument '--user <USER>' cannot be used with '--offline' or '--refresh'",
+                )
+                .exit();
+        }
+    }
+
     match cli.command {
         Commands::Setup => commands::setup(&storage),
         Commands::CleanJiraIssues => commands::clean_jira_issues(&storage).await,
+        Commands::Alias { command } => {
+            match command {
+                AliasCommands::Add {
+                    name,
+                    issue_key,
+                    description,
+                    duration,
+                    billable,
+                    non_billable,
+                } => {
+                    let billable = match (billable, non_billable) {
+                        (true, _) => Some(true),
+                        (_, true) => Some(false),
+                        _ => None,
+                    };
+                    commands::add_alias(
+                        &storage,
+                        &name,
+                        &issue_key,
+                        description,
+                        duration,
+                        billable,
+                    )
+                }
+                AliasCommands::List => commands::list_aliases(&storage),
+                AliasCommands::Remove { name } => commands::remove_alias(&storage, &name),
+            }
+            return;
+        }
         _ => {}
     }
 
@@ -75,20 +503,202 @@ async fn main() {
         std::process::exit(1);
     }
 
-    let api = api::ApiClient::new(storage);
+    let settings = match Settings::load() {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    if cli.user.is_some()
+        && !matches!(
+            cli.command,
+            Commands::List { .. } | Commands::ListRange { .. }
+        )
+    {
+        eprintln!("Error. --user can only be used with list and list-range");
+        std::process::exit(1);
+    }
+
+    let api = api::ApiClient::new(storage, settings);
 
     match cli.command {
         Commands::Setup => {}
         Commands::CleanJiraIssues => {}
-        Commands::List { date } => commands::list(&api, &date).await,
-        Commands::ListRange { date_from, date_to } => {
-            commands::list_range(&api, &date_from, &date_to).await
+        Commands::Alias { .. } => {}
+        Commands::List { date, mirror_args } => {
+            commands::list(&api, &date, cli.user.as_deref(), &mirror_args).await
         }
+        Commands::ListRange {
+            date_from,
+            date_to,
+            mirror_args,
+        } => {
+            commands::list_range(
+                &api,
+                &date_from,
+                &date_to,
+                cli.user.as_deref(),
+                &mirror_args,
+            )
+            .await
+        }
+        Commands::IssueLog {
+            issue_key,
+            date_from,
+            date_to,
+        } => commands::issue_log(&api, &issue_key, date_from, date_to).await,
+        Commands::ProjectLog {
+            project_key,
+            date_from,
+            date_to,
+        } => commands::project_log(&api, &project_key, &date_from, &date_to).await,
         Commands::Log {
             issue_key,
             time_spent,
             comment,
-        } => commands::log_time(&api, &issue_key, &time_spent, comment).await,
-        Commands::Delete { worklog_ids } => commands::delete_log(&api, &worklog_ids).await,
+            split,
+            from_commits,
+            worklog_args,
+            remaining_args,
+            create_options,
+        } => {
+            commands::log_time(
+                &api,
+                issue_key,
+                split,
+                time_spent,
+                comment,
+                from_commits,
+                &worklog_args,
+                &remaining_args,
+                &create_options,
+            )
+            .await
+        }
+        Commands::Edit {
+            worklog_id,
+            time_spent,
+            comment,
+            date,
+            start_time,
+            worklog_args,
+            force,
+        } => {
+            let changes = WorklogChanges {
+                time_spent,
+                comment,
+                date,
+                start_time,
+            };
+            commands::edit_worklog(&api, &worklog_id, &changes, &worklog_args, force).await
+        }
+        Commands::Delete { worklog_ids, force } => {
+            commands::delete_log(&api, &worklog_ids, force).await
+        }
+        Commands::Submit {
+            date,
+            reviewer,
+            comment,
+        } => commands::submit(&api, &date, reviewer, comment).await,
+        Commands::ApprovalStatus { date } => commands::approval_status(&api, &date).await,
+        Commands::Team { command } => match command {
+            TeamCommands::List => commands::list_teams(&api).await,
+            TeamCommands::Week { team, date } => commands::team_week(&api, &team, &date).await,
+            TeamCommands::Approve { review } => {
+                commands::review_timesheet(
+                    &api,
+                    &review.user,
+                    &review.date,
+                    TimesheetReview::Approve,
+                    review.comment,
+                )
+                .await
+            }
+            TeamCommands::Reject { review } => {
+                commands::review_timesheet(
+                    &api,
+                    &review.user,
+                    &review.date,
+                    TimesheetReview::Reject,
+                    review.comment,
+                )
+                .await
+            }
+        },
+        Commands::History { limit } => commands::history(&api.storage, limit),
+        Commands::Undo { count, force } => commands::undo(&api, count, force).await,
+        Commands::Sync { force, discard } => commands::sync(&api, force, &discard).await,
+        Commands::Search { query, jql, limit } => commands::search(&api, &query, jql, limit).await,
+        Commands::Attributes { refresh } => commands::list_work_attributes(&api, refresh).await,
+        Commands::Lint { date_from, date_to } => commands::lint(&api, &date_from, &date_to).await,
+        Commands::ImportCalendar {
+            file,
+            date_from,
+            date_to,
+            rules_file,
+            default_issue,
+            yes,
+            create_options,
+        } => {
+            commands::import_calendar(
+                &api,
+                &file,
+                &date_from,
+                &date_to,
+                rules_file,
+                default_issue,
+                yes,
+                &create_options,
+            )
+            .await
+        }
+        Commands::Import {
+            source,
+            file,
+            date_from,
+            date_to,
+            mapping_file,
+            default_issue,
+            date_format,
+            yes,
+            create_options,
+        } => {
+            commands::import(
+                &api,
+                source,
+                &file,
+                date_from,
+                date_to,
+                mapping_file,
+                default_issue,
+                date_format,
+                yes,
+                &create_options,
+            )
+            .await
+        }
+        Commands::Suggest {
+            date_from,
+            date_to,
+            repos,
+            author,
+            session_cap,
+            yes,
+            create_options,
+        } => {
+            commands::suggest(
+                &api,
+                &date_from,
+                &date_to,
+                &repos,
+                author,
+                &session_cap,
+                yes,
+                &create_options,
+            )
+            .await
+        }
     }
 }
@@ -11,24 +11,63 @@ pub struct UserCredentials {
 
 #[derive(Debug, Deserialize)]
 pub struct UserWorklogsResponse {
+    #[serde(default)]
+    pub metadata: PageMetadata,
     pub results: Vec<WorklogItem>,
 }
 
-#[derive(Debug, Deserialize)]
+// Paging of Tempo results. `next` links to the next page and is left out on the last one
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct PageMetadata {
+    pub count: u32,
+    pub offset: u32,
+    pub limit: u32,
+    pub next: Option<String>,
+}
+
+impl PageMetadata {
+    // A page shorter than the limit is the last one, whatever the links say
+    pub fn next_page(&self) -> Option<&str> {
+        match &self.next {
+            Some(next) if self.count > 0 && self.count >= self.limit => Some(next),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct WorklogItem {
     #[serde(rename = "tempoWorklogId")]
     pub tempo_worklog_id: i64,
     #[serde(rename = "timeSpentSeconds")]
     pub time_spent_seconds: i32,
+    #[serde(rename = "billableSeconds", default)]
+    pub billable_seconds: i32,
     pub description: String,
+    #[serde(rename = "startDate")]
+    pub start_date: String,
+    #[serde(rename = "startTime", default)]
+    pub start_time: String,
     #[serde(rename = "createdAt")]
     pub created_at: String,
+    #[serde(rename = "updatedAt", default)]
+    pub updated_at: String,
     pub issue: TempoIssue,
+    #[serde(default)]
+    pub author: Option<TempoUser>,
+    #[serde(default)]
+    pub attributes: WorklogAttributes,
     #[serde(skip)]
     pub jira_issue: Option<JiraIssue>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct WorklogAttributes {
+    pub values: Vec<WorkAttributeValue>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct TempoIssue {
     pub id: i64,
 }
@@ -37,4 +76,341 @@ pub struct TempoIssue {
 pub struct JiraIssue {
     pub id: String,
     pub key: String,
+    #[serde(default)]
+    pub fields: Option<JiraIssueFields>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JiraIssueFields {
+    pub project: JiraProject,
+    #[serde(default)]
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JiraProject {
+    pub id: String,
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountsResponse {
+    pub results: Vec<Account>,
+}
+
+// Tempo account (cost centre)
+#[derive(Debug, Clone, Deserialize)]
+pub struct Account {
+    pub id: i64,
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub status: String,
+    // Global accounts can be used on any project
+    #[serde(default)]
+    pub global: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountLinksResponse {
+    pub results: Vec<AccountLink>,
+}
+
+// Link between an account and a Jira project
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccountLink {
+    pub account: AccountRef,
+    #[serde(default)]
+    pub default: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccountRef {
+    pub id: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Alias {
+    pub name: String,
+    pub issue_key: String,
+    pub description: Option<String>,
+    pub duration: Option<String>,
+    #[serde(default)]
+    pub billable: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewWorklog {
+    pub issue_key: String,
+    pub time_spent_seconds: i32,
+    pub description: String,
+    pub start_date: String,
+    pub start_time: Option<String>,
+    // None leaves it to Tempo, which bills the whole time spent
+    pub billable_seconds: Option<i32>,
+    pub attributes: Vec<WorkAttributeValue>,
+    // None lets Jira reduce the remaining estimate by the time spent
+    #[serde(default)]
+    pub remaining_estimate_seconds: Option<i32>,
+}
+
+impl NewWorklog {
+    // Worklog that recreates an existing one
+    pub fn from_worklog_item(worklog: &WorklogItem) -> Self {
+        Self {
+            issue_key: match &worklog.jira_issue {
+                Some(issue) => issue.key.clone(),
+                None => worklog.issue.id.to_string(),
+            },
+            time_spent_seconds: worklog.time_spent_seconds,
+            description: worklog.description.clone(),
+            start_date: worklog.start_date.clone(),
+            start_time: match worklog.start_time.as_str() {
+                "" => None,
+                start_time => Some(start_time.to_string()),
+            },
+            billable_seconds: Some(worklog.billable_seconds),
+            attributes: worklog.attributes.values.clone(),
+            remaining_estimate_seconds: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkAttributesResponse {
+    pub results: Vec<WorkAttribute>,
+}
+
+// Work attribute definition
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkAttribute {
+    pub key: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub attribute_type: String,
+    #[serde(default)]
+    pub required: bool,
+    // Allowed values of STATIC_LIST attributes
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkAttributeValue {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PeriodsResponse {
+    pub periods: Vec<Period>,
+}
+
+// Timesheet approval period, e.g a week
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Period {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TimesheetApproval {
+    pub period: Period,
+    #[serde(rename = "requiredSeconds", default)]
+    pub required_seconds: i32,
+    #[serde(rename = "timeSpentSeconds", default)]
+    pub time_spent_seconds: i32,
+    pub status: ApprovalStatus,
+    #[serde(default)]
+    pub reviewer: Option<TempoUser>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApprovalStatus {
+    pub key: String,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl ApprovalStatus {
+    // Short status name, e.g "waiting" for IN_REVIEW
+    pub fn label(&self) -> &str {
+        match self.key.as_str() {
+            "OPEN" => "open",
+            "IN_REVIEW" | "WAITING_FOR_APPROVAL" | "READY_TO_SUBMIT" => "waiting",
+            "APPROVED" => "approved",
+            "REJECTED" => "rejected",
+            _ => "unknown",
+        }
+    }
+
+    pub fn is_approved(&self) -> bool {
+        self.key == "APPROVED"
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TempoUser {
+    #[serde(rename = "accountId")]
+    pub account_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JiraUser {
+    #[serde(rename = "accountId")]
+    pub account_id: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    // Hidden unless the user's profile visibility allows it
+    #[serde(rename = "emailAddress", default)]
+    pub email_address: Option<String>,
+}
+
+// Manager decision on a submitted timesheet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimesheetReview {
+    Approve,
+    Reject,
+}
+
+impl TimesheetReview {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimesheetReview::Approve => "approve",
+            TimesheetReview::Reject => "reject",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamsResponse {
+    pub results: Vec<Team>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Team {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamMembersResponse {
+    pub results: Vec<TeamMember>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TeamMember {
+    pub member: TempoUser,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserScheduleResponse {
+    pub results: Vec<ScheduleDay>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduleDay {
+    pub date: String,
+    #[serde(rename = "requiredSeconds")]
+    pub required_seconds: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JiraTimeTrackingResponse {
+    pub fields: JiraTimeTrackingFields,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JiraTimeTrackingFields {
+    #[serde(default)]
+    pub timetracking: TimeTracking,
+}
+
+// Estimates of a Jira issue. Missing when they were never set
+#[derive(Debug, Default, Deserialize)]
+pub struct TimeTracking {
+    #[serde(rename = "originalEstimateSeconds")]
+    pub original_estimate_seconds: Option<i32>,
+    #[serde(rename = "remainingEstimateSeconds")]
+    pub remaining_estimate_seconds: Option<i32>,
+    #[serde(rename = "timeSpentSeconds")]
+    pub time_spent_seconds: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JiraSearchResponse {
+    pub issues: Vec<JiraIssue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum JournalOperation {
+    Create,
+    Edit,
+    Delete,
+}
+
+// Change tempie made to a worklog, with the worklog before and after it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    #[serde(default)]
+    pub id: u64,
+    pub performed_at: String,
+    pub operation: JournalOperation,
+    pub worklog_id: String,
+    pub before: Option<NewWorklog>,
+    pub after: Option<NewWorklog>,
+    #[serde(default)]
+    pub undone: bool,
+}
+
+// Fields to change in a worklog, None keeps the current value
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorklogChanges {
+    pub time_spent: Option<String>,
+    pub comment: Option<String>,
+    pub date: Option<String>,
+    pub start_time: Option<String>,
+}
+
+// Options of queued worklogs that couldn't be looked up without network. `sync` resolves
+// them into the account, work attributes and remaining estimates
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PendingCreateOptions {
+    pub attributes: Vec<WorkAttributeValue>,
+    pub account: Option<String>,
+    pub remaining: Option<String>,
+    pub reduce_remaining: bool,
+    pub keep_remaining: bool,
+}
+
+// Change queued while the network was unavailable, replayed by `tempie sync`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PendingChange {
+    Create {
+        worklogs: Vec<NewWorklog>,
+        force: bool,
+        // None when the worklogs were queued with their options resolved
+        #[serde(default)]
+        options: Option<PendingCreateOptions>,
+    },
+    Edit {
+        worklog_id: String,
+        changes: WorklogChanges,
+        billable: Option<String>,
+        non_billable: bool,
+        attributes: Vec<WorkAttributeValue>,
+        account: Option<String>,
+        force: bool,
+    },
+    Delete {
+        worklog_id: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingOperation {
+    #[serde(default)]
+    pub id: u64,
+    pub queued_at: String,
+    pub change: PendingChange,
 }
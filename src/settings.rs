@@ -0,0 +1,309 @@
+use crate::storage::Storage;
+use crate::utils::parse_duration_from_string;
+use chrono::NaiveDate;
+use config::ConfigError;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::path::Path;
+
+const SETTINGS_FILE: &str = "config.toml";
+// Settings given as durations, e.g "15m". They're parsed with the [durations] settings
+const DURATION_KEYS: [&str; 4] = [
+    "rounding.increment",
+    "rounding.minimum",
+    "validation.max_per_day",
+    "validation.max_per_worklog",
+];
+
+// User settings from ~/.tempie/config.toml. Every key is optional
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub durations: DurationSettings,
+    pub validation: ValidationSettings,
+    pub rounding: RoundingSettings,
+    pub billable: BillableSettings,
+    pub picker: PickerSettings,
+    pub tempo: TempoSettings,
+}
+
+// How durations without a unit and days are interpreted
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DurationSettings {
+    pub default_unit: DurationUnit,
+    pub hours_per_day: f64,
+}
+
+impl Default for DurationSettings {
+    fn default() -> Self {
+        Self {
+            default_unit: DurationUnit::Minutes,
+            hours_per_day: 8.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DurationUnit {
+    #[serde(alias = "m")]
+    Minutes,
+    #[serde(alias = "h")]
+    Hours,
+}
+
+// How durations are rounded before worklogs are created
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RoundingSettings {
+    pub mode: RoundingMode,
+    pub increment: i32,
+    pub minimum: i32,
+    pub keep_original: KeepOriginal,
+    // Work attribute key the original duration is stored in
+    pub attribute: String,
+}
+
+impl Default for RoundingSettings {
+    fn default() -> Self {
+        Self {
+            mode: RoundingMode::None,
+            increment: 15 * 60,
+            minimum: 0,
+            keep_original: KeepOriginal::None,
+            attribute: "_OriginalDuration_".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundingMode {
+    None,
+    Nearest,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeepOriginal {
+    None,
+    Description,
+    Attribute,
+}
+
+// Whether time is billable, by default and per Jira project
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct BillableSettings {
+    pub default: bool,
+    pub projects: HashMap<String, bool>,
+}
+
+impl Default for BillableSettings {
+    fn default() -> Self {
+        Self {
+            default: true,
+            projects: HashMap::new(),
+        }
+    }
+}
+
+impl BillableSettings {
+    pub fn is_billable(&self, issue_key: &str) -> bool {
+        let project = issue_key.split('-').next().unwrap_or(issue_key);
+
+        self.projects.get(project).copied().unwrap_or(self.default)
+    }
+}
+
+// Issues offered by the interactive picker
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PickerSettings {
+    pub jql: String,
+    pub max_results: u32,
+}
+
+impl Default for PickerSettings {
+    fn default() -> Self {
+        Self {
+            jql: "assignee = currentUser() AND statusCategory = \"In Progress\" ORDER BY updated DESC"
+                .to_string(),
+            max_results: 20,
+        }
+    }
+}
+
+// How worklogs are fetched from Tempo
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TempoSettings {
+    // Worklogs per request, Tempo allows up to 5000
+    pub page_size: u32,
+    // Mirrored months fetched in full longer ago are fetched in full again, the updated
+    // worklogs alone don't tell which ones were deleted
+    pub full_refresh_minutes: i64,
+}
+
+impl Default for TempoSettings {
+    fn default() -> Self {
+        Self {
+            page_size: 1000,
+            full_refresh_minutes: 60,
+        }
+    }
+}
+
+// Rules worklogs are checked against before they're created. Zero durations disable a limit
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ValidationSettings {
+    pub max_per_day: i32,
+    pub max_per_worklog: i32,
+    pub allow_overlaps: bool,
+    pub allow_future: bool,
+    #[serde(deserialize_with = "deserialize_date")]
+    pub closed_until: Option<NaiveDate>,
+}
+
+impl Default for ValidationSettings {
+    fn default() -> Self {
+        Self {
+            max_per_day: 12 * 3600,
+            max_per_worklog: 10 * 3600,
+            allow_overlaps: false,
+            allow_future: false,
+            closed_until: None,
+        }
+    }
+}
+
+impl Settings {
+    // Load the settings from the default file, if there's one
+    pub fn load() -> Result<Self, String> {
+        let settings_file = Storage::get_db_path(SETTINGS_FILE);
+
+        if settings_file.exists() {
+            Self::load_from(&settings_file)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, String> {
+        let error = |e: String| format!("Failed to read settings {}: {}", path.display(), e);
+        let config = config::Config::builder()
+            .add_source(config::File::from(path))
+            .build()
+            .map_err(|e| error(e.to_string()))?;
+
+        // A bare number in a duration setting is in the configured default unit
+        let durations = match config.get::<DurationSettings>("durations") {
+            Ok(durations) => durations,
+            Err(ConfigError::NotFound(_)) => DurationSettings::default(),
+            Err(e) => return Err(error(e.to_string())),
+        };
+
+        let mut seconds = Vec::new();
+        for key in DURATION_KEYS {
+            match config.get_string(key) {
+                Ok(value) => seconds.push((
+                    key,
+                    parse_duration_from_string(&value, &durations)
+                        .map_err(|e| error(format!("{}: {}", key, e)))?,
+                )),
+                Err(ConfigError::NotFound(_)) => {}
+                Err(e) => return Err(error(e.to_string())),
+            }
+        }
+
+        seconds
+            .into_iter()
+            .try_fold(
+                config::Config::builder().add_source(config),
+                |builder, (key, seconds)| builder.set_override(key, seconds),
+            )
+            .and_then(|builder| builder.build())
+            .and_then(|settings| settings.try_deserialize())
+            .map_err(|e| error(e.to_string()))
+    }
+}
+
+fn deserialize_date<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<NaiveDate>, D::Error> {
+    let value = String::deserialize(deserializer)?;
+
+    NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("invalid date '{}'", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_settings() {
+        let path = "test_load_settings.toml";
+        std::fs::write(
+            path,
+            "[durations]\ndefault_unit = \"hours\"\n[validation]\nmax_per_day = \"9h30m\"\nmax_per_worklog = \"0\"\nclosed_until = \"2025-03-31\"\n",
+        )
+        .unwrap();
+
+        let settings = Settings::load_from(Path::new(path)).unwrap();
+        assert_eq!(settings.durations.default_unit, DurationUnit::Hours);
+        assert_eq!(settings.durations.hours_per_day, 8.0);
+        assert_eq!(settings.validation.max_per_day, 34200);
+        assert_eq!(settings.validation.max_per_worklog, 0);
+        assert!(!settings.validation.allow_overlaps);
+        assert_eq!(
+            settings.validation.closed_until,
+            NaiveDate::from_ymd_opt(2025, 3, 31)
+        );
+
+        std::fs::write(
+            path,
+            "[rounding]\nmode = \"up\"\nincrement = \"6m\"\nkeep_original = \"attribute\"\n",
+        )
+        .unwrap();
+        let settings = Settings::load_from(Path::new(path)).unwrap();
+        assert_eq!(settings.rounding.mode, RoundingMode::Up);
+        assert_eq!(settings.rounding.increment, 360);
+        assert_eq!(settings.rounding.minimum, 0);
+        assert_eq!(settings.rounding.keep_original, KeepOriginal::Attribute);
+
+        std::fs::write(
+            path,
+            "[billable]\ndefault = false\n[billable.projects]\nACME = true\n",
+        )
+        .unwrap();
+        let settings = Settings::load_from(Path::new(path)).unwrap();
+        assert!(settings.billable.is_billable("ACME-12"));
+        assert!(!settings.billable.is_billable("INTERNAL-4821"));
+        assert_eq!(settings.tempo.page_size, 1000);
+
+        std::fs::write(path, "[tempo]\npage_size = 200\n").unwrap();
+        let settings = Settings::load_from(Path::new(path)).unwrap();
+        assert_eq!(settings.tempo.page_size, 200);
+
+        // Bare numbers follow the default unit, wherever it's set in the file
+        std::fs::write(
+            path,
+            "[validation]\nmax_per_day = \"9\"\n[rounding]\nminimum = 0.25\n[durations]\ndefault_unit = \"hours\"\n",
+        )
+        .unwrap();
+        let settings = Settings::load_from(Path::new(path)).unwrap();
+        assert_eq!(settings.validation.max_per_day, 32400);
+        assert_eq!(settings.rounding.minimum, 900);
+
+        std::fs::write(path, "[validation]\nmax_per_day = \"a lot\"\n").unwrap();
+        assert!(Settings::load_from(Path::new(path)).is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
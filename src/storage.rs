@@ -1,4 +1,6 @@
-use crate::models::{JiraIssue, UserCredentials};
+use crate::models::{
+    Alias, JiraIssue, JournalEntry, PendingOperation, UserCredentials, WorkAttribute, WorklogItem,
+};
 use serde_json;
 use sled;
 use std::path::PathBuf;
@@ -96,14 +98,375 @@ impl Storage {
             let _old = self.db.remove(key);
         }
 
+        self.db
+            .open_tree("inaccessible_issues")
+            .unwrap()
+            .clear()
+            .unwrap();
         self.db.flush().unwrap();
     }
+
+    // Remember when an issue was found deleted or not visible to the user
+    pub fn store_inaccessible_issue(&self, key_or_id: &str, checked_at: i64) {
+        let inaccessible = self.db.open_tree("inaccessible_issues").unwrap();
+        inaccessible
+            .insert(key_or_id, &checked_at.to_be_bytes())
+            .unwrap();
+        inaccessible.flush().unwrap();
+    }
+
+    pub fn get_inaccessible_issue(&self, key_or_id: &str) -> Option<i64> {
+        self.db
+            .open_tree("inaccessible_issues")
+            .unwrap()
+            .get(key_or_id)
+            .unwrap()
+            .and_then(|v| v.as_ref().try_into().ok())
+            .map(i64::from_be_bytes)
+    }
+
+    // Store issue key alias, overwriting the existing one with the same name
+    pub fn store_alias(&self, alias: &Alias) {
+        let aliases = self.db.open_tree("aliases").unwrap();
+
+        aliases
+            .insert(
+                alias.name.as_str(),
+                serde_json::to_string(alias).unwrap().as_bytes(),
+            )
+            .unwrap();
+
+        aliases.flush().unwrap();
+    }
+
+    // Get issue key alias by its name
+    pub fn get_alias(&self, name: &str) -> Option<Alias> {
+        self.db
+            .open_tree("aliases")
+            .unwrap()
+            .get(name)
+            .ok()
+            .flatten()
+            .and_then(|v| serde_json::from_slice(&v).ok())
+    }
+
+    // Get all issue key aliases sorted by name
+    pub fn list_aliases(&self) -> Vec<Alias> {
+        self.db
+            .open_tree("aliases")
+            .unwrap()
+            .iter()
+            .values()
+            .filter_map(|v| v.ok())
+            .filter_map(|v| serde_json::from_slice(&v).ok())
+            .collect()
+    }
+
+    // Delete issue key alias. Returns false if there was no such alias
+    pub fn delete_alias(&self, name: &str) -> bool {
+        let aliases = self.db.open_tree("aliases").unwrap();
+        let removed = aliases.remove(name).unwrap().is_some();
+
+        aliases.flush().unwrap();
+
+        removed
+    }
+
+    // Expand an alias to the issue key it points to. Anything else is returned as is
+    pub fn expand_alias(&self, issue_or_alias: &str) -> String {
+        self.get_alias(issue_or_alias)
+            .map(|alias| alias.issue_key)
+            .unwrap_or_else(|| issue_or_alias.to_string())
+    }
+
+    // Store work attribute definitions, replacing the cached ones
+    pub fn store_work_attributes(&self, attributes: &[WorkAttribute]) {
+        let tree = self.db.open_tree("work_attributes").unwrap();
+        tree.clear().unwrap();
+
+        for attribute in attributes {
+            tree.insert(
+                attribute.key.as_str(),
+                serde_json::to_string(attribute).unwrap().as_bytes(),
+            )
+            .unwrap();
+        }
+
+        tree.flush().unwrap();
+    }
+
+    // Add an entry to the journal of worklog changes. Entries are numbered in order
+    pub fn add_journal_entry(&self, mut entry: JournalEntry) -> JournalEntry {
+        let journal = self.db.open_tree("journal").unwrap();
+        entry.id = self.db.generate_id().unwrap();
+
+        journal
+            .insert(
+                entry.id.to_be_bytes(),
+                serde_json::to_string(&entry).unwrap().as_bytes(),
+            )
+            .unwrap();
+
+        journal.flush().unwrap();
+
+        entry
+    }
+
+    // Overwrite a journal entry, e.g to mark it undone
+    pub fn update_journal_entry(&self, entry: &JournalEntry) {
+        let journal = self.db.open_tree("journal").unwrap();
+
+        journal
+            .insert(
+                entry.id.to_be_bytes(),
+                serde_json::to_string(entry).unwrap().as_bytes(),
+            )
+            .unwrap();
+
+        journal.flush().unwrap();
+    }
+
+    // Get the latest journal entries, latest first
+    pub fn list_journal_entries(&self, limit: usize) -> Vec<JournalEntry> {
+        self.db
+            .open_tree("journal")
+            .unwrap()
+            .iter()
+            .rev()
+            .filter_map(|entry| entry.ok())
+            .filter_map(|(_, v)| serde_json::from_slice(&v).ok())
+            .take(limit)
+            .collect()
+    }
+
+    // A worklog that's recreated gets a new ID. Point its journal entries to it
+    pub fn replace_journal_worklog_id(&self, old_id: &str, new_id: &str) {
+        for mut entry in self.list_journal_entries(usize::MAX) {
+            if entry.worklog_id == old_id {
+                entry.worklog_id = new_id.to_string();
+                self.update_journal_entry(&entry);
+            }
+        }
+    }
+
+    // Store a worklog in the local mirror, indexed by its date
+    pub fn store_mirrored_worklog(&self, worklog: &WorklogItem) {
+        let worklogs = self.db.open_tree("worklogs").unwrap();
+        let by_date = self.db.open_tree("worklogs_by_date").unwrap();
+        let id = worklog.tempo_worklog_id.to_string();
+
+        if let Some(previous) = worklogs.get(&id).unwrap() {
+            if let Ok(previous) = serde_json::from_slice::<WorklogItem>(&previous) {
+                by_date
+                    .remove(date_index_key(&previous.start_date, &id))
+                    .unwrap();
+            }
+        }
+
+        worklogs
+            .insert(&id, serde_json::to_string(worklog).unwrap().as_bytes())
+            .unwrap();
+        by_date
+            .insert(date_index_key(&worklog.start_date, &id), id.as_bytes())
+            .unwrap();
+
+        worklogs.flush().unwrap();
+        by_date.flush().unwrap();
+    }
+
+    pub fn remove_mirrored_worklog(&self, worklog_id: &str) {
+        let worklogs = self.db.open_tree("worklogs").unwrap();
+        let by_date = self.db.open_tree("worklogs_by_date").unwrap();
+
+        if let Some(previous) = worklogs.remove(worklog_id).unwrap() {
+            if let Ok(previous) = serde_json::from_slice::<WorklogItem>(&previous) {
+                by_date
+                    .remove(date_index_key(&previous.start_date, worklog_id))
+                    .unwrap();
+            }
+        }
+
+        worklogs.flush().unwrap();
+        by_date.flush().unwrap();
+    }
+
+    // Get the mirrored worklogs between the dates, ordered by date
+    pub fn get_mirrored_worklogs(&self, date_from: &str, date_to: &str) -> Vec<WorklogItem> {
+        let worklogs = self.db.open_tree("worklogs").unwrap();
+
+        self.db
+            .open_tree("worklogs_by_date")
+            .unwrap()
+            .range(format!("{}/", date_from)..format!("{}/~", date_to))
+            .filter_map(|entry| entry.ok())
+            .filter_map(|(_, id)| worklogs.get(id).ok().flatten())
+            .filter_map(|v| serde_json::from_slice(&v).ok())
+            .collect()
+    }
+
+    // Replace the mirrored worklogs between the dates, dropping the ones deleted in Tempo
+    pub fn replace_mirrored_worklogs(
+        &self,
+        date_from: &str,
+        date_to: &str,
+        worklogs: &[WorklogItem],
+    ) {
+        for worklog in self.get_mirrored_worklogs(date_from, date_to) {
+            self.remove_mirrored_worklog(&worklog.tempo_worklog_id.to_string());
+        }
+
+        for worklog in worklogs {
+            self.store_mirrored_worklog(worklog);
+        }
+    }
+
+    // When the mirror of a month was last refreshed, e.g "2025-04"
+    pub fn store_mirror_refreshed_at(&self, month: &str, refreshed_at: &str) {
+        let mirror = self.db.open_tree("mirror").unwrap();
+        mirror.insert(month, refreshed_at.as_bytes()).unwrap();
+        mirror.flush().unwrap();
+    }
+
+    pub fn get_mirror_refreshed_at(&self, month: &str) -> Option<String> {
+        self.db
+            .open_tree("mirror")
+            .unwrap()
+            .get(month)
+            .unwrap()
+            .map(|v| String::from_utf8(v.to_vec()).unwrap())
+    }
+
+    // When the mirror of a month was last fetched in full
+    pub fn store_mirror_fetched_at(&self, month: &str, fetched_at: &str) {
+        let mirror = self.db.open_tree("mirror").unwrap();
+        mirror
+            .insert(format!("{}/full", month), fetched_at.as_bytes())
+            .unwrap();
+        mirror.flush().unwrap();
+    }
+
+    pub fn get_mirror_fetched_at(&self, month: &str) -> Option<String> {
+        self.db
+            .open_tree("mirror")
+            .unwrap()
+            .get(format!("{}/full", month))
+            .unwrap()
+            .map(|v| String::from_utf8(v.to_vec()).unwrap())
+    }
+
+    // Queue a change to replay later, in the order the changes were queued
+    pub fn add_pending_operation(&self, mut operation: PendingOperation) -> PendingOperation {
+        let pending = self.db.open_tree("pending").unwrap();
+        operation.id = self.db.generate_id().unwrap();
+
+        pending
+            .insert(
+                operation.id.to_be_bytes(),
+                serde_json::to_string(&operation).unwrap().as_bytes(),
+            )
+            .unwrap();
+
+        pending.flush().unwrap();
+
+        operation
+    }
+
+    // Get the queued changes, oldest first
+    pub fn list_pending_operations(&self) -> Vec<PendingOperation> {
+        self.db
+            .open_tree("pending")
+            .unwrap()
+            .iter()
+            .filter_map(|entry| entry.ok())
+            .filter_map(|(_, v)| serde_json::from_slice(&v).ok())
+            .collect()
+    }
+
+    pub fn remove_pending_operation(&self, id: u64) -> bool {
+        let pending = self.db.open_tree("pending").unwrap();
+        let removed = pending.remove(id.to_be_bytes()).unwrap().is_some();
+        pending.flush().unwrap();
+
+        removed
+    }
+
+    // Remember when time was last logged on an issue
+    pub fn store_recent_issue(&self, issue_key: &str, used_at: i64) {
+        let recent = self.db.open_tree("recent_issues").unwrap();
+
+        recent
+            .insert(issue_key, used_at.to_be_bytes().as_slice())
+            .unwrap();
+
+        recent.flush().unwrap();
+    }
+
+    // Get the issues time was logged on most recently, latest first
+    pub fn get_recent_issues(&self, limit: usize) -> Vec<String> {
+        let mut recent: Vec<(String, i64)> = self
+            .db
+            .open_tree("recent_issues")
+            .unwrap()
+            .iter()
+            .filter_map(|entry| entry.ok())
+            .filter_map(|(key, value)| {
+                let used_at = i64::from_be_bytes(value.as_ref().try_into().ok()?);
+                Some((String::from_utf8(key.to_vec()).ok()?, used_at))
+            })
+            .collect();
+
+        recent.sort_by_key(|(_, used_at)| std::cmp::Reverse(*used_at));
+        recent
+            .into_iter()
+            .take(limit)
+            .map(|(issue_key, _)| issue_key)
+            .collect()
+    }
+
+    // Cache the account ID of a user's email
+    pub fn store_user_account_id(&self, email: &str, account_id: &str) {
+        let users = self.db.open_tree("users").unwrap();
+
+        users
+            .insert(email.to_lowercase().as_str(), account_id.as_bytes())
+            .unwrap();
+
+        users.flush().unwrap();
+    }
+
+    // Get the cached account ID of a user's email
+    pub fn get_user_account_id(&self, email: &str) -> Option<String> {
+        self.db
+            .open_tree("users")
+            .unwrap()
+            .get(email.to_lowercase().as_str())
+            .ok()
+            .flatten()
+            .and_then(|v| String::from_utf8(v.to_vec()).ok())
+    }
+
+    // Get cached work attribute definitions sorted by key
+    pub fn get_work_attributes(&self) -> Vec<WorkAttribute> {
+        self.db
+            .open_tree("work_attributes")
+            .unwrap()
+            .iter()
+            .values()
+            .filter_map(|v| v.ok())
+            .filter_map(|v| serde_json::from_slice(&v).ok())
+            .collect()
+    }
+}
+
+fn date_index_key(date: &str, worklog_id: &str) -> String {
+    format!("{}/{}", date, worklog_id)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::models::{JournalOperation, PendingChange, TempoIssue, WorklogAttributes};
     use std::fs;
 
     fn cleanup_test_db(path: &str) {
@@ -152,6 +515,7 @@ mod tests {
         let test_issue = JiraIssue {
             id: "12345".to_string(),
             key: "TEST-123".to_string(),
+            fields: None,
         };
 
         assert!(storage.get_jira_issue(&test_issue.key).is_none());
@@ -222,6 +586,7 @@ mod tests {
         storage.store_jira_issue(&JiraIssue {
             id: "12345".to_string(),
             key: "TEST-123".to_string(),
+            fields: None,
         });
 
         storage.delete_jira_issues();
@@ -233,4 +598,265 @@ mod tests {
 
         cleanup_test_db(test_db_path);
     }
+
+    #[test]
+    fn test_storage_aliases() {
+        let test_db_path = "test_storage_aliases";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+
+        assert!(storage.get_alias("standup").is_none());
+        assert_eq!(storage.expand_alias("standup"), "standup");
+
+        storage.store_alias(&Alias {
+            name: "standup".to_string(),
+            issue_key: "INTERNAL-4821".to_string(),
+            description: Some("Daily standup".to_string()),
+            duration: Some("15m".to_string()),
+            billable: None,
+        });
+        storage.store_alias(&Alias {
+            name: "review".to_string(),
+            issue_key: "INTERNAL-1".to_string(),
+            description: None,
+            duration: None,
+            billable: None,
+        });
+
+        let alias = storage.get_alias("standup").expect("Failed to get alias");
+        assert_eq!(alias.issue_key, "INTERNAL-4821");
+        assert_eq!(alias.description, Some("Daily standup".to_string()));
+        assert_eq!(alias.duration, Some("15m".to_string()));
+
+        assert_eq!(storage.expand_alias("standup"), "INTERNAL-4821");
+        assert_eq!(storage.expand_alias("ABC-1"), "ABC-1");
+
+        let names: Vec<String> = storage.list_aliases().into_iter().map(|a| a.name).collect();
+        assert_eq!(names, vec!["review", "standup"]);
+
+        // Aliases survive cleaning the issue cache
+        storage.delete_jira_issues();
+        assert!(storage.get_alias("standup").is_some());
+
+        assert!(storage.delete_alias("standup"));
+        assert!(!storage.delete_alias("standup"));
+        assert!(storage.get_alias("standup").is_none());
+
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_storage_work_attributes() {
+        let test_db_path = "test_storage_work_attributes";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+
+        assert!(storage.get_work_attributes().is_empty());
+
+        let attribute = |key: &str| WorkAttribute {
+            key: key.to_string(),
+            name: key.trim_matches('_').to_string(),
+            attribute_type: "STATIC_LIST".to_string(),
+            required: false,
+            values: vec!["Development".to_string(), "Meeting".to_string()],
+        };
+
+        storage.store_work_attributes(&[attribute("_WorkType_"), attribute("_Activity_")]);
+        let keys: Vec<String> = storage
+            .get_work_attributes()
+            .into_iter()
+            .map(|a| a.key)
+            .collect();
+        assert_eq!(keys, vec!["_Activity_", "_WorkType_"]);
+
+        // Stored definitions replace the cached ones
+        storage.store_work_attributes(&[attribute("_WorkType_")]);
+        let attributes = storage.get_work_attributes();
+        assert_eq!(attributes.len(), 1);
+        assert_eq!(attributes[0].values, vec!["Development", "Meeting"]);
+
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_storage_user_account_id() {
+        let test_db_path = "test_storage_user_account_id";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+
+        assert_eq!(storage.get_user_account_id("jane@example.com"), None);
+
+        storage.store_user_account_id("Jane@Example.com", "5b10ac8d82e05b22cc7d4ef5");
+        assert_eq!(
+            storage.get_user_account_id("jane@example.com"),
+            Some("5b10ac8d82e05b22cc7d4ef5".to_string())
+        );
+
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_storage_recent_issues() {
+        let test_db_path = "test_storage_recent_issues";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+
+        assert!(storage.get_recent_issues(10).is_empty());
+
+        storage.store_recent_issue("ABC-1", 100);
+        storage.store_recent_issue("ABC-2", 300);
+        storage.store_recent_issue("ABC-3", 200);
+        storage.store_recent_issue("ABC-1", 400);
+
+        assert_eq!(
+            storage.get_recent_issues(10),
+            vec!["ABC-1", "ABC-2", "ABC-3"]
+        );
+        assert_eq!(storage.get_recent_issues(2), vec!["ABC-1", "ABC-2"]);
+
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_storage_journal() {
+        let test_db_path = "test_storage_journal";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+
+        let entry = |worklog_id: &str, operation| JournalEntry {
+            id: 0,
+            performed_at: "2025-04-07T10:00:00+02:00".to_string(),
+            operation,
+            worklog_id: worklog_id.to_string(),
+            before: None,
+            after: None,
+            undone: false,
+        };
+
+        storage.add_journal_entry(entry("1", JournalOperation::Create));
+        let mut deleted = storage.add_journal_entry(entry("1", JournalOperation::Delete));
+        storage.add_journal_entry(entry("2", JournalOperation::Create));
+
+        let entries = storage.list_journal_entries(2);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].worklog_id, "2");
+        assert_eq!(entries[1], deleted);
+
+        deleted.undone = true;
+        storage.update_journal_entry(&deleted);
+        storage.replace_journal_worklog_id("1", "3");
+
+        let entries = storage.list_journal_entries(10);
+        assert_eq!(entries.len(), 3);
+        assert!(entries[1].undone);
+        assert_eq!(entries[1].worklog_id, "3");
+        assert_eq!(entries[2].worklog_id, "3");
+
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_storage_pending_operations() {
+        let test_db_path = "test_storage_pending_operations";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+
+        let operation = |worklog_id: &str| PendingOperation {
+            id: 0,
+            queued_at: "2025-04-07T10:00:00+02:00".to_string(),
+            change: PendingChange::Delete {
+                worklog_id: worklog_id.to_string(),
+            },
+        };
+
+        let first = storage.add_pending_operation(operation("1"));
+        let second = storage.add_pending_operation(operation("2"));
+
+        assert_eq!(
+            storage.list_pending_operations(),
+            vec![first.clone(), second.clone()]
+        );
+
+        assert!(storage.remove_pending_operation(first.id));
+        assert!(!storage.remove_pending_operation(first.id));
+        assert_eq!(storage.list_pending_operations(), vec![second]);
+
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_storage_mirrored_worklogs() {
+        let test_db_path = "test_storage_mirrored_worklogs";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+
+        let worklog = |id: i64, date: &str| WorklogItem {
+            tempo_worklog_id: id,
+            time_spent_seconds: 3600,
+            billable_seconds: 3600,
+            description: "Review".to_string(),
+            start_date: date.to_string(),
+            start_time: String::new(),
+            created_at: "2025-04-07T10:00:00Z".to_string(),
+            updated_at: String::new(),
+            issue: TempoIssue { id: 123 },
+            author: None,
+            attributes: WorklogAttributes::default(),
+            jira_issue: None,
+        };
+        let ids = |worklogs: Vec<WorklogItem>| -> Vec<i64> {
+            worklogs.iter().map(|w| w.tempo_worklog_id).collect()
+        };
+
+        storage.store_mirrored_worklog(&worklog(2, "2025-04-08"));
+        storage.store_mirrored_worklog(&worklog(1, "2025-04-07"));
+        storage.store_mirrored_worklog(&worklog(3, "2025-05-01"));
+        assert_eq!(
+            ids(storage.get_mirrored_worklogs("2025-04-01", "2025-04-30")),
+            vec![1, 2]
+        );
+
+        // A worklog moved to another date is found on the new date only
+        storage.store_mirrored_worklog(&worklog(2, "2025-05-02"));
+        assert_eq!(
+            ids(storage.get_mirrored_worklogs("2025-04-01", "2025-04-30")),
+            vec![1]
+        );
+        assert_eq!(
+            ids(storage.get_mirrored_worklogs("2025-05-01", "2025-05-31")),
+            vec![3, 2]
+        );
+
+        storage.remove_mirrored_worklog("3");
+        storage.replace_mirrored_worklogs("2025-04-01", "2025-04-30", &[worklog(4, "2025-04-09")]);
+        assert_eq!(
+            ids(storage.get_mirrored_worklogs("2025-04-01", "2025-05-31")),
+            vec![4, 2]
+        );
+
+        assert_eq!(storage.get_mirror_refreshed_at("2025-04"), None);
+        storage.store_mirror_refreshed_at("2025-04", "2025-04-09T10:00:00Z");
+        assert_eq!(
+            storage.get_mirror_refreshed_at("2025-04").as_deref(),
+            Some("2025-04-09T10:00:00Z")
+        );
+
+        cleanup_test_db(test_db_path);
+    }
+
+    #[test]
+    fn test_storage_inaccessible_issues() {
+        let test_db_path = "test_storage_inaccessible_issues";
+        cleanup_test_db(test_db_path);
+        let storage = Storage::with_path(test_db_path);
+
+        assert_eq!(storage.get_inaccessible_issue("10004"), None);
+        storage.store_inaccessible_issue("10004", 1743984000);
+        assert_eq!(storage.get_inaccessible_issue("10004"), Some(1743984000));
+
+        storage.delete_jira_issues();
+        assert_eq!(storage.get_inaccessible_issue("10004"), None);
+
+        cleanup_test_db(test_db_path);
+    }
 }
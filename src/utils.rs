@@ -1,17 +1,102 @@
+use crate::settings::{DurationSettings, DurationUnit, RoundingMode, RoundingSettings};
 use crate::storage::Storage;
 use chrono::{Datelike, Local, NaiveDate, Weekday};
-use humantime::parse_duration;
 
 const WORKING_HOURS_PER_DAY: i32 = 8;
 const SECONDS_PER_HOUR: i32 = 3600;
 
-// Parse a duration from a string. hours:minutes -> seconds
-pub fn parse_duration_from_string(duration_str: &str) -> i32 {
-    if duration_str.is_empty() {
-        return 0;
+// Parse a duration from a string into seconds, e.g "1h30m", "1.5h", "1:30", "90", "1d 2h"
+// or "2h-15m". Bare numbers use the default unit, and a day is the configured day length
+pub fn parse_duration_from_string(
+    duration_str: &str,
+    settings: &DurationSettings,
+) -> Result<i32, String> {
+    let invalid = || format!("Invalid duration: '{}'", duration_str);
+    let expression: String = duration_str.split_whitespace().collect();
+
+    if expression.is_empty() || !expression.is_ascii() || expression.starts_with(['+', '-']) {
+        return Err(invalid());
     }
 
-    parse_duration(duration_str).unwrap().as_secs() as i32
+    let mut total = 0.0;
+    let mut rest = expression.as_str();
+    let mut sign = 1.0;
+
+    loop {
+        let end = rest[1..].find(['+', '-']).map_or(rest.len(), |i| i + 1);
+        let (term, next) = rest.split_at(end);
+        let term = term.strip_prefix(['+', '-']).unwrap_or(term);
+
+        total += sign * parse_duration_term(term, settings).ok_or_else(invalid)?;
+
+        match next.chars().next() {
+            Some(operator) => {
+                sign = if operator == '-' { -1.0 } else { 1.0 };
+                rest = next;
+            }
+            None => break,
+        }
+    }
+
+    if total < 0.0 {
+        return Err(format!("Negative duration: '{}'", duration_str));
+    }
+    if !total.is_finite() || total.round() > i32::MAX as f64 {
+        return Err(format!("Duration too long: '{}'", duration_str));
+    }
+
+    Ok(total.round() as i32)
+}
+
+// Parse a single duration term into seconds, e.g "1h30m", "1.5h", "1:30" or "90"
+fn parse_duration_term(term: &str, settings: &DurationSettings) -> Option<f64> {
+    if term.is_empty() {
+        return None;
+    }
+
+    if let Some((hours, minutes)) = term.split_once(':') {
+        if minutes.len() != 2 {
+            return None;
+        }
+
+        let hours: u32 = hours.parse().ok()?;
+        let minutes: u32 = minutes.parse().ok().filter(|minutes| *minutes < 60)?;
+
+        return Some(hours as f64 * 3600.0 + minutes as f64 * 60.0);
+    }
+
+    if term.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        let value: f64 = term.parse().ok()?;
+
+        return Some(match settings.default_unit {
+            DurationUnit::Minutes => value * 60.0,
+            DurationUnit::Hours => value * 3600.0,
+        });
+    }
+
+    let mut seconds = 0.0;
+    let mut rest = term;
+
+    while !rest.is_empty() {
+        let number_end = rest.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let unit_end = rest[number_end..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .map_or(rest.len(), |i| i + number_end);
+
+        let value: f64 = rest[..number_end].parse().ok()?;
+        let unit_seconds = match &rest[number_end..unit_end] {
+            "d" | "day" | "days" => settings.hours_per_day * 3600.0,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3600.0,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60.0,
+            "s" | "sec" | "secs" | "second" | "seconds" => 1.0,
+            _ => return None,
+        };
+
+        seconds += value * unit_seconds;
+        rest = &rest[unit_end..];
+    }
+
+    Some(seconds)
 }
 
 // Format a duration in hours and minutes. seconds -> hours:minutes
@@ -35,6 +120,66 @@ pub fn format_duration(seconds: i32) -> String {
     format!("{}h{}m", hours, minutes)
 }
 
+// Split a duration into parts proportional to the weights. The parts are rounded to whole
+// minutes using the largest remainder, so they always add up exactly to the total
+pub fn split_duration(total_seconds: i32, weights: &[u32]) -> Vec<i32> {
+    let total_weight: i64 = weights.iter().map(|&weight| weight as i64).sum();
+
+    if weights.is_empty() || total_weight == 0 {
+        return vec![0; weights.len()];
+    }
+
+    let total_minutes = (total_seconds / 60) as i64;
+    let mut parts: Vec<i64> = weights
+        .iter()
+        .map(|&weight| total_minutes * weight as i64 / total_weight)
+        .collect();
+
+    // Hand out the minutes lost to rounding down, biggest remainders first
+    let mut by_remainder: Vec<usize> = (0..weights.len()).collect();
+    by_remainder
+        .sort_by_key(|&i| std::cmp::Reverse(total_minutes * weights[i] as i64 % total_weight));
+
+    let leftover = total_minutes - parts.iter().sum::<i64>();
+    for &i in by_remainder.iter().take(leftover as usize) {
+        parts[i] += 1;
+    }
+
+    let mut parts: Vec<i32> = parts
+        .into_iter()
+        .map(|minutes| minutes as i32 * 60)
+        .collect();
+    parts[0] += total_seconds % 60;
+
+    parts
+}
+
+// Round a duration to the configured increment and bring it up to the minimum. Nearest
+// never rounds a duration that isn't zero down to zero, Down leaves that to the minimum
+pub fn round_duration(seconds: i32, settings: &RoundingSettings) -> i32 {
+    if settings.mode == RoundingMode::None || seconds <= 0 {
+        return seconds;
+    }
+
+    let (seconds, increment) = (i64::from(seconds), i64::from(settings.increment));
+    let rounded = if increment > 0 {
+        match settings.mode {
+            RoundingMode::Nearest => {
+                ((seconds + increment / 2) / increment * increment).max(increment)
+            }
+            RoundingMode::Up => (seconds + increment - 1) / increment * increment,
+            _ => seconds / increment * increment,
+        }
+    } else {
+        seconds
+    };
+
+    // Rounded past the limit, the last increment that fits is taken
+    i32::try_from(rounded)
+        .unwrap_or_else(|_| i32::MAX / settings.increment * settings.increment)
+        .max(settings.minimum)
+}
+
 // Get how many working hours in a current month
 pub fn working_seconds_in_month(date: &str) -> i32 {
     let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
@@ -135,31 +280,63 @@ mod tests {
 
     #[test]
     fn test_parse_duration_from_string() {
-        // Test hours
-        assert_eq!(parse_duration_from_string("1h"), 3600);
-        assert_eq!(parse_duration_from_string("2h"), 7200);
+        let settings = DurationSettings::default();
+        let parse = |duration: &str| parse_duration_from_string(duration, &settings);
 
         // Test hours
-        assert_eq!(parse_duration_from_string("1h"), 3600);
-        assert_eq!(parse_duration_from_string("2h"), 7200);
+        assert_eq!(parse("1h"), Ok(3600));
+        assert_eq!(parse("2h"), Ok(7200));
 
         // Test minutes
-        assert_eq!(parse_duration_from_string("30m"), 1800);
-        assert_eq!(parse_duration_from_string("45m"), 2700);
+        assert_eq!(parse("30m"), Ok(1800));
+        assert_eq!(parse("45m"), Ok(2700));
 
         // Test hours and minutes
-        assert_eq!(parse_duration_from_string("1h30m"), 5400);
-        assert_eq!(parse_duration_from_string("2h45m"), 9900);
+        assert_eq!(parse("1h30m"), Ok(5400));
+        assert_eq!(parse("2h 45m"), Ok(9900));
+
+        // Test days, a day is a working day
+        assert_eq!(parse("1d"), Ok(28800));
+        assert_eq!(parse("1d 2h"), Ok(36000));
+
+        // Test decimals, clock format and bare numbers
+        assert_eq!(parse("1.5h"), Ok(5400));
+        assert_eq!(parse("0.25h"), Ok(900));
+        assert_eq!(parse("1:30"), Ok(5400));
+        assert_eq!(parse("0:05"), Ok(300));
+        assert_eq!(parse("90"), Ok(5400));
 
-        // Test days
-        assert_eq!(parse_duration_from_string("1d"), 86400);
-        assert_eq!(parse_duration_from_string("2d"), 172800);
+        // Test arithmetic
+        assert_eq!(parse("2h-15m"), Ok(6300));
+        assert_eq!(parse("1h + 30m + 1:00"), Ok(9000));
 
         // Test edge cases
-        assert_eq!(parse_duration_from_string("0h"), 0);
-        assert_eq!(parse_duration_from_string("0m"), 0);
-        assert_eq!(parse_duration_from_string("55s"), 55);
-        assert_eq!(parse_duration_from_string(""), 0);
+        assert_eq!(parse("0h"), Ok(0));
+        assert_eq!(parse("0m"), Ok(0));
+        assert_eq!(parse("55s"), Ok(55));
+        assert!(parse("").is_err());
+        assert!(parse("abc").is_err());
+        assert!(parse("1x").is_err());
+        assert!(parse("1:75").is_err());
+        assert!(parse("1h-").is_err());
+        assert!(parse("h").is_err());
+        assert!(parse("15m-1h").is_err());
+        assert!(parse("-1h").is_err());
+        assert!(parse("1h--30m").is_err());
+        assert!(parse("2000000:00").is_err());
+        assert!(parse("10000000h").is_err());
+        assert!(parse(&"9".repeat(400)).is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_with_settings() {
+        let settings = DurationSettings {
+            default_unit: DurationUnit::Hours,
+            hours_per_day: 7.5,
+        };
+
+        assert_eq!(parse_duration_from_string("2", &settings), Ok(7200));
+        assert_eq!(parse_duration_from_string("1d", &settings), Ok(27000));
     }
 
     #[test]
@@ -177,6 +354,84 @@ mod tests {
         assert_eq!(format_duration(61), "1m");
     }
 
+    #[test]
+    fn test_split_duration() {
+        assert_eq!(split_duration(10800, &[1, 1, 1]), vec![3600, 3600, 3600]);
+        assert_eq!(split_duration(10800, &[50, 30, 20]), vec![5400, 3240, 2160]);
+        assert_eq!(split_duration(3600, &[1, 1, 1]), vec![1200, 1200, 1200]);
+
+        // Rounding leftovers are distributed so the parts add up to the total
+        assert_eq!(split_duration(600, &[1, 1, 1]), vec![240, 180, 180]);
+        assert_eq!(
+            split_duration(3600, &[33, 33, 34]).iter().sum::<i32>(),
+            3600
+        );
+        assert_eq!(split_duration(3630, &[1, 1]), vec![1830, 1800]);
+
+        // Edge cases
+        assert_eq!(split_duration(3600, &[]), Vec::<i32>::new());
+        assert_eq!(split_duration(3600, &[0, 0]), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_round_duration() {
+        let settings = |mode| RoundingSettings {
+            mode,
+            ..RoundingSettings::default()
+        };
+
+        assert_eq!(round_duration(47 * 60, &settings(RoundingMode::None)), 2820);
+        assert_eq!(
+            round_duration(47 * 60, &settings(RoundingMode::Nearest)),
+            2700
+        );
+        assert_eq!(
+            round_duration(53 * 60, &settings(RoundingMode::Nearest)),
+            3600
+        );
+        assert_eq!(round_duration(31 * 60, &settings(RoundingMode::Up)), 2700);
+        assert_eq!(round_duration(44 * 60, &settings(RoundingMode::Down)), 1800);
+        assert_eq!(round_duration(45 * 60, &settings(RoundingMode::Up)), 2700);
+
+        // Short entries aren't rounded away to the nearest increment, rounding down is left to
+        // the minimum
+        assert_eq!(
+            round_duration(5 * 60, &settings(RoundingMode::Nearest)),
+            900
+        );
+        assert_eq!(round_duration(5 * 60, &settings(RoundingMode::Down)), 0);
+        assert_eq!(round_duration(0, &settings(RoundingMode::Up)), 0);
+        assert_eq!(
+            round_duration(
+                5 * 60,
+                &RoundingSettings {
+                    minimum: 60,
+                    ..settings(RoundingMode::Down)
+                }
+            ),
+            60
+        );
+
+        // No overflow near the limit
+        assert_eq!(
+            round_duration(i32::MAX, &settings(RoundingMode::Up)),
+            i32::MAX / 900 * 900
+        );
+        assert_eq!(
+            round_duration(i32::MAX, &settings(RoundingMode::Nearest)),
+            i32::MAX / 900 * 900
+        );
+
+        let with_minimum = RoundingSettings {
+            mode: RoundingMode::Nearest,
+            increment: 5 * 60,
+            minimum: 30 * 60,
+            ..RoundingSettings::default()
+        };
+        assert_eq!(round_duration(12 * 60, &with_minimum), 1800);
+        assert_eq!(round_duration(47 * 60, &with_minimum), 2700);
+    }
+
     #[test]
     fn test_get_month_name() {
         assert_eq!(get_month_name("2025-04-01"), "April");
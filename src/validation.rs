@@ -0,0 +1,464 @@
+use crate::models::{NewWorklog, Period, WorkAttribute, WorkAttributeValue, WorklogItem};
+use crate::settings::ValidationSettings;
+use crate::utils::format_duration;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::collections::BTreeMap;
+
+// Worklog reduced to what the rules look at
+struct Entry {
+    label: String,
+    date: NaiveDate,
+    start_time: Option<NaiveTime>,
+    seconds: i32,
+}
+
+impl Entry {
+    fn from_new_worklog(worklog: &NewWorklog) -> Self {
+        Self {
+            label: format!("{} on {}", worklog.issue_key, worklog.start_date),
+            date: parse_date(&worklog.start_date),
+            start_time: worklog.start_time.as_deref().and_then(parse_start_time),
+            seconds: worklog.time_spent_seconds,
+        }
+    }
+
+    fn from_worklog_item(worklog: &WorklogItem) -> Self {
+        let label = match &worklog.jira_issue {
+            Some(issue) => format!("worklog {} ({})", worklog.tempo_worklog_id, issue.key),
+            None => format!("worklog {}", worklog.tempo_worklog_id),
+        };
+
+        Self {
+            label,
+            date: parse_date(&worklog.start_date),
+            start_time: parse_start_time(&worklog.start_time),
+            seconds: worklog.time_spent_seconds,
+        }
+    }
+
+    // Worklogs can run past midnight, so the full date times are compared
+    fn overlaps(&self, other: &Entry) -> bool {
+        match (self.time_range(), other.time_range()) {
+            (Some((start, end)), Some((other_start, other_end))) => {
+                start < other_end && other_start < end
+            }
+            _ => false,
+        }
+    }
+
+    fn time_range(&self) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        let start = self.date.and_time(self.start_time?);
+
+        Some((
+            start,
+            start + chrono::Duration::seconds(self.seconds as i64),
+        ))
+    }
+}
+
+// Check worklogs about to be created against the rules and the worklogs already logged
+pub fn validate_new_worklogs(
+    worklogs: &[NewWorklog],
+    existing: &[WorklogItem],
+    rules: &ValidationSettings,
+    today: NaiveDate,
+) -> Vec<String> {
+    let new: Vec<Entry> = worklogs.iter().map(Entry::from_new_worklog).collect();
+    let existing: Vec<Entry> = existing.iter().map(Entry::from_worklog_item).collect();
+
+    let mut violations: Vec<String> = match rules.closed_until {
+        Some(closed_until) => new
+            .iter()
+            .filter(|entry| entry.date <= closed_until)
+            .map(|entry| {
+                format!(
+                    "{} falls into a closed period (until {})",
+                    entry.label, closed_until
+                )
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    violations.extend(find_violations(&new, &existing, rules, today));
+    violations
+}
+
+// Find the worklogs that fall into approved timesheet periods. `changed` are existing
+// worklogs about to be updated, which can't be moved out of an approved period either
+pub fn check_approved_periods(
+    worklogs: &[NewWorklog],
+    changed: &[WorklogItem],
+    periods: &[Period],
+) -> Vec<String> {
+    let entries = worklogs
+        .iter()
+        .map(Entry::from_new_worklog)
+        .chain(changed.iter().map(Entry::from_worklog_item));
+
+    entries
+        .filter_map(|entry| {
+            periods
+                .iter()
+                .find(|p| parse_date(&p.from) <= entry.date && entry.date <= parse_date(&p.to))
+                .map(|p| {
+                    format!(
+                        "{} falls into an approved period ({} to {})",
+                        entry.label, p.from, p.to
+                    )
+                })
+        })
+        .collect()
+}
+
+// Check worklogs that are already logged against the rules
+pub fn lint_worklogs(
+    worklogs: &[WorklogItem],
+    rules: &ValidationSettings,
+    today: NaiveDate,
+) -> Vec<String> {
+    let entries: Vec<Entry> = worklogs.iter().map(Entry::from_worklog_item).collect();
+
+    find_violations(&entries, &[], rules, today)
+}
+
+// Apply the rules to the `checked` entries. The `others` only count towards the daily
+// totals and overlaps
+fn find_violations(
+    checked: &[Entry],
+    others: &[Entry],
+    rules: &ValidationSettings,
+    today: NaiveDate,
+) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    for entry in checked {
+        if rules.max_per_worklog > 0 && entry.seconds > rules.max_per_worklog {
+            violations.push(format!(
+                "{} is {}, more than the {} allowed per worklog",
+                entry.label,
+                format_duration(entry.seconds),
+                format_duration(rules.max_per_worklog)
+            ));
+        }
+
+        if !rules.allow_future && entry.date > today {
+            violations.push(format!("{} is in the future", entry.label));
+        }
+    }
+
+    if rules.max_per_day > 0 {
+        let mut totals: BTreeMap<NaiveDate, i32> =
+            checked.iter().map(|entry| (entry.date, 0)).collect();
+
+        for entry in checked.iter().chain(others) {
+            if let Some(total) = totals.get_mut(&entry.date) {
+                *total += entry.seconds;
+            }
+        }
+
+        for (date, total) in totals {
+            if total > rules.max_per_day {
+                violations.push(format!(
+                    "{} would have {} logged, more than the {} allowed per day",
+                    date,
+                    format_duration(total),
+                    format_duration(rules.max_per_day)
+                ));
+            }
+        }
+    }
+
+    if !rules.allow_overlaps {
+        for (i, entry) in checked.iter().enumerate() {
+            for other in checked[i + 1..].iter().chain(others) {
+                if entry.overlaps(other) {
+                    violations.push(format!("{} overlaps {}", entry.label, other.label));
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+// Check work attribute values against their definitions. Attributes can be given by key or
+// name, and static list values in any case. Returns the values with canonical keys and values
+pub fn validate_work_attributes(
+    attributes: &[WorkAttributeValue],
+    definitions: &[WorkAttribute],
+) -> Result<Vec<WorkAttributeValue>, String> {
+    attributes
+        .iter()
+        .map(|attribute| {
+            let definition = definitions
+                .iter()
+                .find(|d| d.key == attribute.key || d.name.eq_ignore_ascii_case(&attribute.key))
+                .ok_or_else(|| format!("Unknown work attribute: '{}'", attribute.key))?;
+
+            let value = if definition.attribute_type == "STATIC_LIST" {
+                definition
+                    .values
+                    .iter()
+                    .find(|value| value.eq_ignore_ascii_case(&attribute.value))
+                    .cloned()
+                    .ok_or_else(|| {
+                        format!(
+                            "Invalid value '{}' for {}. Expected one of: {}",
+                            attribute.value,
+                            definition.name,
+                            definition.values.join(", ")
+                        )
+                    })?
+            } else {
+                attribute.value.clone()
+            };
+
+            Ok(WorkAttributeValue {
+                key: definition.key.clone(),
+                value,
+            })
+        })
+        .collect()
+}
+
+// Find the required work attributes that have no value
+pub fn missing_work_attributes<'a>(
+    attributes: &[WorkAttributeValue],
+    definitions: &'a [WorkAttribute],
+) -> Vec<&'a WorkAttribute> {
+    definitions
+        .iter()
+        .filter(|d| d.required && !attributes.iter().any(|a| a.key == d.key))
+        .collect()
+}
+
+fn parse_date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+// Worklogs logged without a time start at midnight, so midnight means unknown
+fn parse_start_time(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time, "%H:%M:%S")
+        .ok()
+        .filter(|time| *time != NaiveTime::MIN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TempoIssue, WorklogAttributes};
+
+    fn new_worklog(date: &str, start_time: Option<&str>, seconds: i32) -> NewWorklog {
+        NewWorklog {
+            issue_key: "ABC-1".to_string(),
+            time_spent_seconds: seconds,
+            description: String::new(),
+            start_date: date.to_string(),
+            start_time: start_time.map(str::to_string),
+            billable_seconds: None,
+            attributes: Vec::new(),
+            remaining_estimate_seconds: None,
+        }
+    }
+
+    fn worklog_item(id: i64, date: &str, start_time: &str, seconds: i32) -> WorklogItem {
+        WorklogItem {
+            tempo_worklog_id: id,
+            time_spent_seconds: seconds,
+            billable_seconds: seconds,
+            description: String::new(),
+            start_date: date.to_string(),
+            start_time: start_time.to_string(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            issue: TempoIssue { id: 100 },
+            author: None,
+            attributes: WorklogAttributes::default(),
+            jira_issue: None,
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 4, 10).unwrap()
+    }
+
+    #[test]
+    fn test_validate_new_worklogs() {
+        let rules = ValidationSettings {
+            closed_until: NaiveDate::from_ymd_opt(2025, 3, 31),
+            ..ValidationSettings::default()
+        };
+        let existing = vec![
+            worklog_item(1, "2025-04-07", "09:00:00", 4 * 3600),
+            worklog_item(2, "2025-04-07", "00:00:00", 4 * 3600),
+        ];
+
+        let valid = vec![new_worklog("2025-04-07", Some("13:00:00"), 3600)];
+        assert!(validate_new_worklogs(&valid, &existing, &rules, today()).is_empty());
+
+        let overlapping = vec![new_worklog("2025-04-07", Some("12:30:00"), 3600)];
+        assert_eq!(
+            validate_new_worklogs(&overlapping, &existing, &rules, today()),
+            vec!["ABC-1 on 2025-04-07 overlaps worklog 1"]
+        );
+
+        let too_long = vec![new_worklog("2025-04-08", None, 11 * 3600)];
+        let violations = validate_new_worklogs(&too_long, &existing, &rules, today());
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("allowed per worklog"));
+
+        let too_much = vec![
+            new_worklog("2025-04-07", None, 3 * 3600),
+            new_worklog("2025-04-07", None, 2 * 3600),
+        ];
+        assert_eq!(
+            validate_new_worklogs(&too_much, &existing, &rules, today()),
+            vec!["2025-04-07 would have 13h logged, more than the 12h allowed per day"]
+        );
+
+        let future = vec![new_worklog("2025-04-11", None, 3600)];
+        assert_eq!(
+            validate_new_worklogs(&future, &existing, &rules, today()),
+            vec!["ABC-1 on 2025-04-11 is in the future"]
+        );
+
+        let closed = vec![new_worklog("2025-03-31", None, 3600)];
+        assert_eq!(
+            validate_new_worklogs(&closed, &existing, &rules, today()),
+            vec!["ABC-1 on 2025-03-31 falls into a closed period (until 2025-03-31)"]
+        );
+    }
+
+    #[test]
+    fn test_overlaps_across_midnight() {
+        let rules = ValidationSettings::default();
+        let late = vec![new_worklog("2025-04-07", Some("23:00:00"), 2 * 3600)];
+
+        let existing = vec![worklog_item(1, "2025-04-07", "23:30:00", 1800)];
+        assert_eq!(
+            validate_new_worklogs(&late, &existing, &rules, today()),
+            vec!["ABC-1 on 2025-04-07 overlaps worklog 1"]
+        );
+
+        let existing = vec![worklog_item(1, "2025-04-08", "00:30:00", 1800)];
+        assert_eq!(
+            validate_new_worklogs(&late, &existing, &rules, today()),
+            vec!["ABC-1 on 2025-04-07 overlaps worklog 1"]
+        );
+
+        let existing = vec![worklog_item(1, "2025-04-08", "01:00:00", 1800)];
+        assert!(validate_new_worklogs(&late, &existing, &rules, today()).is_empty());
+    }
+
+    #[test]
+    fn test_validation_rules_can_be_disabled() {
+        let rules = ValidationSettings {
+            max_per_day: 0,
+            max_per_worklog: 0,
+            allow_overlaps: true,
+            allow_future: true,
+            closed_until: None,
+        };
+        let existing = vec![worklog_item(1, "2025-04-07", "09:00:00", 4 * 3600)];
+        let worklogs = vec![
+            new_worklog("2025-04-07", Some("10:00:00"), 20 * 3600),
+            new_worklog("2025-05-01", None, 3600),
+        ];
+
+        assert!(validate_new_worklogs(&worklogs, &existing, &rules, today()).is_empty());
+    }
+
+    #[test]
+    fn test_check_approved_periods() {
+        let periods = vec![Period {
+            from: "2025-03-31".to_string(),
+            to: "2025-04-06".to_string(),
+        }];
+
+        let worklogs = vec![
+            new_worklog("2025-04-06", None, 3600),
+            new_worklog("2025-04-07", None, 3600),
+        ];
+        assert_eq!(
+            check_approved_periods(&worklogs, &[], &periods),
+            vec!["ABC-1 on 2025-04-06 falls into an approved period (2025-03-31 to 2025-04-06)"]
+        );
+
+        let moved_out = vec![worklog_item(7, "2025-04-01", "00:00:00", 3600)];
+        assert_eq!(
+            check_approved_periods(&worklogs[1..], &moved_out, &periods),
+            vec!["worklog 7 falls into an approved period (2025-03-31 to 2025-04-06)"]
+        );
+        assert!(check_approved_periods(&worklogs[1..], &[], &periods).is_empty());
+    }
+
+    #[test]
+    fn test_lint_worklogs() {
+        let rules = ValidationSettings::default();
+        let worklogs = vec![
+            worklog_item(1, "2025-04-07", "09:00:00", 2 * 3600),
+            worklog_item(2, "2025-04-07", "10:00:00", 3600),
+            worklog_item(3, "2025-04-08", "09:00:00", 3600),
+            worklog_item(4, "2025-04-08", "10:00:00", 3600),
+        ];
+
+        assert_eq!(
+            lint_worklogs(&worklogs, &rules, today()),
+            vec!["worklog 1 overlaps worklog 2"]
+        );
+    }
+
+    #[test]
+    fn test_validate_work_attributes() {
+        let definitions = vec![
+            WorkAttribute {
+                key: "_WorkType_".to_string(),
+                name: "Work type".to_string(),
+                attribute_type: "STATIC_LIST".to_string(),
+                required: true,
+                values: vec!["Development".to_string(), "Meeting".to_string()],
+            },
+            WorkAttribute {
+                key: "_Ticket_".to_string(),
+                name: "Ticket".to_string(),
+                attribute_type: "INPUT_FIELD".to_string(),
+                required: false,
+                values: Vec::new(),
+            },
+        ];
+        let attribute = |key: &str, value: &str| WorkAttributeValue {
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+
+        assert_eq!(
+            validate_work_attributes(
+                &[
+                    attribute("work type", "development"),
+                    attribute("_Ticket_", "SUP-1")
+                ],
+                &definitions
+            ),
+            Ok(vec![
+                attribute("_WorkType_", "Development"),
+                attribute("_Ticket_", "SUP-1")
+            ])
+        );
+        assert_eq!(
+            validate_work_attributes(&[attribute("_WorkType_", "Lunch")], &definitions),
+            Err(
+                "Invalid value 'Lunch' for Work type. Expected one of: Development, Meeting"
+                    .to_string()
+            )
+        );
+        assert!(validate_work_attributes(&[attribute("_Color_", "Red")], &definitions).is_err());
+
+        let missing = missing_work_attributes(&[attribute("_Ticket_", "SUP-1")], &definitions);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].key, "_WorkType_");
+        assert!(
+            missing_work_attributes(&[attribute("_WorkType_", "Meeting")], &definitions).is_empty()
+        );
+    }
+}
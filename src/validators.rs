@@ -9,6 +9,12 @@ pub fn validate_iso8601_date(s: &str) -> Result<String, String> {
         })
 }
 
+// Only the syntax is checked here, the default unit and day length are applied later
+pub fn validate_duration(s: &str) -> Result<String, String> {
+    crate::utils::parse_duration_from_string(s, &crate::settings::DurationSettings::default())
+        .map(|_| s.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -24,4 +30,14 @@ mod tests {
             Err("Invalid date format or wrong date: '2025-04-35'. Expected YYYY-MM-DD".to_string())
         );
     }
+
+    #[test]
+    fn test_validate_duration() {
+        assert_eq!(validate_duration("1h30m"), Ok("1h30m".to_string()));
+        assert_eq!(validate_duration("1:30"), Ok("1:30".to_string()));
+        assert_eq!(
+            validate_duration("1 hour and a bit"),
+            Err("Invalid duration: '1 hour and a bit'".to_string())
+        );
+    }
 }